@@ -0,0 +1,200 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Borrowed GBLN Value Representation
+//!
+//! A zero-copy counterpart of [`crate::Value`] produced by
+//! [`crate::parse_borrowed`]. Keys and string values are `Cow<'a, str>`
+//! slices of the input; numbers, booleans and null are stored inline.
+//! Call [`Value::into_owned`] to detach the result from the input.
+
+use crate::config::ParseOptions;
+use crate::decimal::Decimal;
+use crate::error::Error;
+use crate::map::Map;
+use crate::temporal::{Date, DateTime, Time, Timestamp};
+use crate::types::TypeHint;
+use std::borrow::Cow;
+
+/// A GBLN value borrowing its text from the parsed input
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    // Signed integers
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+
+    // Unsigned integers
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+
+    // Floats
+    F32(f32),
+    F64(f64),
+
+    // Exact decimal
+    Decimal(Decimal),
+
+    // String (borrowed from the input where possible)
+    Str(Cow<'a, str>),
+
+    // Temporal
+    Date(Date),
+    Time(Time),
+    DateTime(DateTime),
+    Timestamp(Timestamp),
+
+    // Binary (always decoded, so never borrowed)
+    Bytes(Vec<u8>),
+
+    // Boolean
+    Bool(bool),
+
+    // Null
+    Null,
+
+    // Object (preserves insertion order)
+    Object(Map<Cow<'a, str>, Value<'a>>),
+
+    // Array
+    Array(Vec<Value<'a>>),
+}
+
+impl<'a> Value<'a> {
+    /// Convert into an owned [`crate::Value`], copying borrowed text
+    pub fn into_owned(self) -> crate::Value {
+        match self {
+            Value::I8(n) => crate::Value::I8(n),
+            Value::I16(n) => crate::Value::I16(n),
+            Value::I32(n) => crate::Value::I32(n),
+            Value::I64(n) => crate::Value::I64(n),
+            Value::I128(n) => crate::Value::I128(n),
+            Value::U8(n) => crate::Value::U8(n),
+            Value::U16(n) => crate::Value::U16(n),
+            Value::U32(n) => crate::Value::U32(n),
+            Value::U64(n) => crate::Value::U64(n),
+            Value::U128(n) => crate::Value::U128(n),
+            Value::F32(n) => crate::Value::F32(n),
+            Value::F64(n) => crate::Value::F64(n),
+            Value::Decimal(d) => crate::Value::Decimal(d),
+            Value::Str(s) => crate::Value::Str(s.into_owned()),
+            Value::Date(d) => crate::Value::Date(d),
+            Value::Time(t) => crate::Value::Time(t),
+            Value::DateTime(dt) => crate::Value::DateTime(dt),
+            Value::Timestamp(ts) => crate::Value::Timestamp(ts),
+            Value::Bytes(b) => crate::Value::Bytes(b),
+            Value::Bool(b) => crate::Value::Bool(b),
+            Value::Null => crate::Value::Null,
+            Value::Object(map) => {
+                crate::Value::Object(map.convert(Cow::into_owned, Value::into_owned))
+            }
+            Value::Array(arr) => {
+                crate::Value::Array(arr.into_iter().map(Value::into_owned).collect())
+            }
+        }
+    }
+
+    /// Parse a value from raw content according to a type hint
+    ///
+    /// String values keep `s` as is; other types are parsed as by
+    /// [`TypeHint::parse_value`].
+    pub fn parse_typed(hint: &TypeHint, s: Cow<'a, str>) -> Result<Self, Error> {
+        Value::parse_typed_with_options(hint, s, &ParseOptions::default())
+    }
+
+    /// Parse a value from raw content, accepting the literal spellings of
+    /// the dialect in `options`
+    pub fn parse_typed_with_options(
+        hint: &TypeHint,
+        s: Cow<'a, str>,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        match hint {
+            TypeHint::Str(_) | TypeHint::ConstrainedStr(..) | TypeHint::Enum(_) => {
+                hint.check_str(&s)?;
+                Ok(Value::Str(s))
+            }
+            TypeHint::Nullable(inner) if !options.dialect.is_null(&s) => {
+                Value::parse_typed_with_options(inner, s, options)
+            }
+            _ => hint.parse_value_with_options(&s, options).map(Value::from),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        if let Value::Str(s) = self {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map<Cow<'a, str>, Value<'a>>> {
+        if let Value::Object(obj) = self {
+            Some(obj)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value<'a>>> {
+        if let Value::Array(arr) = self {
+            Some(arr)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<crate::Value> for Value<'_> {
+    fn from(value: crate::Value) -> Self {
+        match value {
+            crate::Value::I8(n) => Value::I8(n),
+            crate::Value::I16(n) => Value::I16(n),
+            crate::Value::I32(n) => Value::I32(n),
+            crate::Value::I64(n) => Value::I64(n),
+            crate::Value::I128(n) => Value::I128(n),
+            crate::Value::U8(n) => Value::U8(n),
+            crate::Value::U16(n) => Value::U16(n),
+            crate::Value::U32(n) => Value::U32(n),
+            crate::Value::U64(n) => Value::U64(n),
+            crate::Value::U128(n) => Value::U128(n),
+            crate::Value::F32(n) => Value::F32(n),
+            crate::Value::F64(n) => Value::F64(n),
+            crate::Value::Decimal(d) => Value::Decimal(d),
+            crate::Value::Str(s) => Value::Str(Cow::Owned(s)),
+            crate::Value::Date(d) => Value::Date(d),
+            crate::Value::Time(t) => Value::Time(t),
+            crate::Value::DateTime(dt) => Value::DateTime(dt),
+            crate::Value::Timestamp(ts) => Value::Timestamp(ts),
+            crate::Value::Bytes(b) => Value::Bytes(b),
+            crate::Value::Bool(b) => Value::Bool(b),
+            crate::Value::Null => Value::Null,
+            crate::Value::Object(map) => Value::Object(map.convert(Cow::Owned, Value::from)),
+            crate::Value::Array(arr) => Value::Array(arr.into_iter().map(Value::from).collect()),
+        }
+    }
+}
+
+impl<'a> From<Value<'a>> for crate::Value {
+    fn from(value: Value<'a>) -> Self {
+        value.into_owned()
+    }
+}
+
+// Index access for objects (syntactic sugar)
+impl<'a> std::ops::Index<&str> for Value<'a> {
+    type Output = Value<'a>;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        match self {
+            Value::Object(map) => map.get(key).unwrap_or(&Value::Null),
+            _ => &Value::Null,
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Equality, hashing and ordering for Value
+//!
+//! Floats are canonicalised before comparison so that `Value` can be `Eq`,
+//! `Hash` and `Ord`: all NaNs collapse to a single quiet NaN and `-0.0`
+//! becomes `0.0`. See the documentation of [`Value`] for the full rules.
+
+use crate::decimal::Decimal;
+use crate::map::Map;
+use crate::value::Value;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Canonical form of an `f32`: one NaN, no negative zero
+fn canonical_f32(n: f32) -> f32 {
+    if n.is_nan() {
+        f32::NAN
+    } else if n == 0.0 {
+        0.0
+    } else {
+        n
+    }
+}
+
+/// Canonical form of an `f64`: one NaN, no negative zero
+fn canonical_f64(n: f64) -> f64 {
+    if n.is_nan() {
+        f64::NAN
+    } else if n == 0.0 {
+        0.0
+    } else {
+        n
+    }
+}
+
+impl Value {
+    /// Position of the variant in declaration order (for cross-variant ordering)
+    fn rank(&self) -> u8 {
+        match self {
+            Value::I8(_) => 0,
+            Value::I16(_) => 1,
+            Value::I32(_) => 2,
+            Value::I64(_) => 3,
+            Value::I128(_) => 4,
+            Value::U8(_) => 5,
+            Value::U16(_) => 6,
+            Value::U32(_) => 7,
+            Value::U64(_) => 8,
+            Value::U128(_) => 9,
+            Value::F32(_) => 10,
+            Value::F64(_) => 11,
+            Value::Decimal(_) => 12,
+            Value::Str(_) => 13,
+            Value::Date(_) => 14,
+            Value::Time(_) => 15,
+            Value::DateTime(_) => 16,
+            Value::Timestamp(_) => 17,
+            Value::Bytes(_) => 18,
+            Value::Bool(_) => 19,
+            Value::Null => 20,
+            Value::Object(_) => 21,
+            Value::Array(_) => 22,
+        }
+    }
+
+    /// Compare by meaning rather than representation
+    ///
+    /// Numbers are equal if they have the same mathematical value, whatever
+    /// their width or kind: `U8(5)`, `I64(5)` and `F64(5.0)` are all equal,
+    /// while `F32(0.1)` and `F64(0.1)` are not (they are different numbers).
+    /// Decimals equal integers and decimals of the same value (`1.50` and
+    /// `1.5`), but never floats. Floats are canonicalised as for `==`.
+    /// Objects and arrays are compared recursively; all other values compare
+    /// as with `==`.
+    ///
+    /// ```
+    /// use gbln::{parse, Value};
+    ///
+    /// let typed = parse("cfg{port<u16>(8080)}").unwrap();
+    /// let untyped = parse("cfg{port(8080)}").unwrap();
+    /// assert_ne!(typed, untyped);
+    /// assert!(typed.semantic_eq(&untyped));
+    /// ```
+    pub fn semantic_eq(&self, other: &Value) -> bool {
+        if let (Some(a), Some(b)) = (self.as_integer(), other.as_integer()) {
+            return a == b;
+        }
+        if let (Some(a), Some(b)) = (self.as_f64(), other.as_f64()) {
+            return canonical_f64(a).total_cmp(&canonical_f64(b)) == Ordering::Equal;
+        }
+        if let (Some(n), Some(f)) = (self.as_integer(), other.as_f64()) {
+            return float_equals_integer(f, n);
+        }
+        if let (Some(f), Some(n)) = (self.as_f64(), other.as_integer()) {
+            return float_equals_integer(f, n);
+        }
+        if self.is_decimal() || other.is_decimal() {
+            return match (exact_decimal(self), exact_decimal(other)) {
+                (Some(a), Some(b)) => a.numeric_cmp(&b) == Ordering::Equal,
+                _ => false,
+            };
+        }
+
+        match (self, other) {
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|bv| v.semantic_eq(bv)))
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.semantic_eq(y))
+            }
+            _ => self == other,
+        }
+    }
+}
+
+/// A decimal or integer value as a decimal
+fn exact_decimal(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Decimal(d) => Some(*d),
+        _ => Decimal::new(value.as_integer()?, 0).ok(),
+    }
+}
+
+/// Check whether a float holds exactly the given integer
+fn float_equals_integer(f: f64, n: i128) -> bool {
+    // 2^127 is exactly representable; anything at or beyond it cannot be an i128
+    const LIMIT: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+    f.fract() == 0.0 && (-LIMIT..LIMIT).contains(&f) && f as i128 == n
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::I8(a), Value::I8(b)) => a == b,
+            (Value::I16(a), Value::I16(b)) => a == b,
+            (Value::I32(a), Value::I32(b)) => a == b,
+            (Value::I64(a), Value::I64(b)) => a == b,
+            (Value::I128(a), Value::I128(b)) => a == b,
+            (Value::U8(a), Value::U8(b)) => a == b,
+            (Value::U16(a), Value::U16(b)) => a == b,
+            (Value::U32(a), Value::U32(b)) => a == b,
+            (Value::U64(a), Value::U64(b)) => a == b,
+            (Value::U128(a), Value::U128(b)) => a == b,
+            (Value::F32(a), Value::F32(b)) => {
+                canonical_f32(*a).to_bits() == canonical_f32(*b).to_bits()
+            }
+            (Value::F64(a), Value::F64(b)) => {
+                canonical_f64(*a).to_bits() == canonical_f64(*b).to_bits()
+            }
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Date(a), Value::Date(b)) => a == b,
+            (Value::Time(a), Value::Time(b)) => a == b,
+            (Value::DateTime(a), Value::DateTime(b)) => a == b,
+            (Value::Timestamp(a), Value::Timestamp(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Null, Value::Null) => true,
+            (Value::Object(a), Value::Object(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            Value::I8(n) => n.hash(state),
+            Value::I16(n) => n.hash(state),
+            Value::I32(n) => n.hash(state),
+            Value::I64(n) => n.hash(state),
+            Value::I128(n) => n.hash(state),
+            Value::U8(n) => n.hash(state),
+            Value::U16(n) => n.hash(state),
+            Value::U32(n) => n.hash(state),
+            Value::U64(n) => n.hash(state),
+            Value::U128(n) => n.hash(state),
+            Value::F32(n) => canonical_f32(*n).to_bits().hash(state),
+            Value::F64(n) => canonical_f64(*n).to_bits().hash(state),
+            Value::Decimal(d) => d.hash(state),
+            Value::Str(s) => s.hash(state),
+            Value::Date(d) => d.hash(state),
+            Value::Time(t) => t.hash(state),
+            Value::DateTime(dt) => dt.hash(state),
+            Value::Timestamp(ts) => ts.hash(state),
+            Value::Bytes(b) => b.hash(state),
+            Value::Bool(b) => b.hash(state),
+            Value::Null => {}
+            Value::Object(map) => map.hash(state),
+            Value::Array(arr) => arr.hash(state),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::I8(a), Value::I8(b)) => a.cmp(b),
+            (Value::I16(a), Value::I16(b)) => a.cmp(b),
+            (Value::I32(a), Value::I32(b)) => a.cmp(b),
+            (Value::I64(a), Value::I64(b)) => a.cmp(b),
+            (Value::I128(a), Value::I128(b)) => a.cmp(b),
+            (Value::U8(a), Value::U8(b)) => a.cmp(b),
+            (Value::U16(a), Value::U16(b)) => a.cmp(b),
+            (Value::U32(a), Value::U32(b)) => a.cmp(b),
+            (Value::U64(a), Value::U64(b)) => a.cmp(b),
+            (Value::U128(a), Value::U128(b)) => a.cmp(b),
+            (Value::F32(a), Value::F32(b)) => canonical_f32(*a).total_cmp(&canonical_f32(*b)),
+            (Value::F64(a), Value::F64(b)) => canonical_f64(*a).total_cmp(&canonical_f64(*b)),
+            (Value::Decimal(a), Value::Decimal(b)) => a.cmp(b),
+            (Value::Str(a), Value::Str(b)) => a.cmp(b),
+            (Value::Date(a), Value::Date(b)) => a.cmp(b),
+            (Value::Time(a), Value::Time(b)) => a.cmp(b),
+            (Value::DateTime(a), Value::DateTime(b)) => a.cmp(b),
+            (Value::Timestamp(a), Value::Timestamp(b)) => a.cmp(b),
+            (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Object(a), Value::Object(b)) => a.cmp(b),
+            (Value::Array(a), Value::Array(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+// Objects are unordered for comparison purposes: hashing and ordering go
+// through the fields sorted by key, consistent with order-insensitive `==`.
+
+impl Map {
+    fn sorted_fields(&self) -> Vec<(&String, &Value)> {
+        let mut fields: Vec<_> = self.iter().collect();
+        fields.sort_by(|a, b| a.0.cmp(b.0));
+        fields
+    }
+}
+
+impl Eq for Map {}
+
+impl Hash for Map {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sorted_fields().hash(state);
+    }
+}
+
+impl PartialOrd for Map {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Map {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted_fields().cmp(&other.sorted_fields())
+    }
+}
@@ -4,7 +4,8 @@
 //! Configuration for GBLN I/O operations
 //!
 //! This module provides the `GblnConfig` struct for configuring how GBLN files
-//! are serialised and written to disk.
+//! are serialised and written to disk, and `ParseOptions` for how they are
+//! read.
 
 /// Configuration for GBLN I/O operations
 ///
@@ -39,8 +40,19 @@ pub struct GblnConfig {
     /// Indentation width for pretty-printing (ignored if mini_mode is true)
     pub indent: usize,
 
-    /// Strip comments when generating I/O format
+    /// Strip comments when generating I/O format; if false, pretty output
+    /// re-emits field doc comments (`:|| ...`)
     pub strip_comments: bool,
+
+    /// Emit object fields sorted by key instead of in insertion order
+    pub sort_keys: bool,
+
+    /// Infer type hints from values instead of re-emitting declared ones
+    pub reinfer_types: bool,
+
+    /// Write NaN and infinite floats (`nan`, `inf`, `-inf`); if false,
+    /// serialising them fails with `ErrorKind::NonFiniteFloat`
+    pub allow_non_finite: bool,
 }
 
 impl Default for GblnConfig {
@@ -51,6 +63,9 @@ impl Default for GblnConfig {
     /// - `compression_level`: 6
     /// - `indent`: 2
     /// - `strip_comments`: true
+    /// - `sort_keys`: false
+    /// - `reinfer_types`: false
+    /// - `allow_non_finite`: true
     fn default() -> Self {
         Self {
             mini_mode: true,
@@ -58,6 +73,9 @@ impl Default for GblnConfig {
             compression_level: 6,
             indent: 2,
             strip_comments: true,
+            sort_keys: false,
+            reinfer_types: false,
+            allow_non_finite: true,
         }
     }
 }
@@ -72,6 +90,9 @@ impl GblnConfig {
     /// - `compression_level`: 6 (unused)
     /// - `indent`: 2
     /// - `strip_comments`: false
+    /// - `sort_keys`: false
+    /// - `reinfer_types`: false
+    /// - `allow_non_finite`: true
     pub fn development() -> Self {
         Self {
             mini_mode: false,
@@ -79,6 +100,9 @@ impl GblnConfig {
             compression_level: 6,
             indent: 2,
             strip_comments: false,
+            sort_keys: false,
+            reinfer_types: false,
+            allow_non_finite: true,
         }
     }
 
@@ -91,6 +115,9 @@ impl GblnConfig {
     /// - `compression_level`: 6
     /// - `indent`: 2 (unused)
     /// - `strip_comments`: true
+    /// - `sort_keys`: false
+    /// - `reinfer_types`: false
+    /// - `allow_non_finite`: true
     pub fn io_format() -> Self {
         Self::default()
     }
@@ -140,4 +167,183 @@ impl GblnConfig {
         self.strip_comments = strip;
         self
     }
+
+    /// Set whether to sort object keys (canonical output)
+    pub fn sort_keys(mut self, sort: bool) -> Self {
+        self.sort_keys = sort;
+        self
+    }
+
+    /// Set whether to infer type hints from values, discarding the ones
+    /// declared in the parsed source
+    pub fn reinfer_types(mut self, reinfer: bool) -> Self {
+        self.reinfer_types = reinfer;
+        self
+    }
+
+    /// Set whether NaN and infinite floats may be written
+    pub fn allow_non_finite(mut self, allow: bool) -> Self {
+        self.allow_non_finite = allow;
+        self
+    }
+}
+
+/// Spellings of scalar literals accepted by the parser
+///
+/// Applies to typed values (`<b>(yes)`) and to type inference for untyped
+/// ones alike, except that untyped `yes`, `no` and `n` stay strings unless
+/// [`ParseOptions::infer_aliases`] is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// Canonical spellings only
+    ///
+    /// - booleans: `t`, `f`, `true`, `false`
+    /// - null: empty content or `null`
+    /// - numbers: decimal notation (`-12`, `3.25`), optionally with a
+    ///   lowercase exponent (`1e300`, `-2.5e-8`); no `+` sign
+    /// - non-finite floats: `nan`, `inf` and `-inf`, in typed values only
+    ///   (untyped they are strings)
+    Strict,
+
+    /// Every documented alias in typed values
+    ///
+    /// Untyped `yes`, `no` and `n` stay strings unless
+    /// [`ParseOptions::infer_aliases`] is set as well, as it is by
+    /// [`ParseOptions::lenient`] but not by [`ParseOptions::new`]: `k<b>(yes)`
+    /// is `true` either way, `k(yes)` only with the aliases.
+    ///
+    /// - booleans: also `0`, `1`, `yes`, `no` (untyped `0` and `1` are always
+    ///   inferred as integers)
+    /// - null: also `n`
+    /// - numbers: anything Rust parses, including `+5`, `1e5`, `inf` and
+    ///   `NaN`
+    #[default]
+    Lenient,
+}
+
+impl Dialect {
+    /// Check for a spelling of null
+    pub(crate) fn is_null(self, s: &str) -> bool {
+        s.is_empty() || s == "null" || (self == Dialect::Lenient && s == "n")
+    }
+
+    /// Read a spelling of a boolean
+    pub(crate) fn parse_bool(self, s: &str) -> Option<bool> {
+        match (self, s) {
+            (_, "t" | "true") => Some(true),
+            (_, "f" | "false") => Some(false),
+            (Dialect::Lenient, "1" | "yes") => Some(true),
+            (Dialect::Lenient, "0" | "no") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Check that typed float text is spelled in a way this dialect accepts:
+    /// a number, or a canonical non-finite spelling
+    pub(crate) fn allows_float(self, s: &str) -> bool {
+        self.allows_number(s) || matches!(s, "nan" | "inf" | "-inf")
+    }
+
+    /// Check that numeric text is spelled in a way this dialect accepts
+    ///
+    /// Only restricts the notation; whether the text is a valid number of
+    /// the wanted type is left to the caller.
+    pub(crate) fn allows_number(self, s: &str) -> bool {
+        if self == Dialect::Lenient {
+            return true;
+        }
+        let digits = |t: &str| !t.is_empty() && t.bytes().all(|c| c.is_ascii_digit());
+        let unsigned = s.strip_prefix('-').unwrap_or(s);
+        let (mantissa, exponent) = match unsigned.split_once('e') {
+            Some((mantissa, exp)) => (mantissa, Some(exp.strip_prefix('-').unwrap_or(exp))),
+            None => (unsigned, None),
+        };
+        let mantissa = match mantissa.split_once('.') {
+            Some((int, frac)) => digits(int) && digits(frac),
+            None => digits(mantissa),
+        };
+        mantissa && exponent.is_none_or(digits)
+    }
+}
+
+/// Options for parsing GBLN text
+///
+/// # Examples
+///
+/// ```
+/// use gbln::{parse_with_options, ParseOptions, Value};
+///
+/// let lenient = parse_with_options("flag<b>(yes)", &ParseOptions::lenient()).unwrap();
+/// assert_eq!(lenient["flag"], Value::Bool(true));
+///
+/// assert!(parse_with_options("flag<b>(yes)", &ParseOptions::strict()).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// Accepted spellings of booleans, null and numbers
+    pub dialect: Dialect,
+
+    /// Accept NaN and infinite floats; if false, they fail with
+    /// `ErrorKind::NonFiniteFloat`
+    pub allow_non_finite: bool,
+
+    /// Infer untyped `yes`/`no` as booleans and `n` as null in the lenient
+    /// dialect; if false, they stay strings as in plain [`parse`](crate::parse)
+    pub infer_aliases: bool,
+}
+
+impl Default for ParseOptions {
+    /// Options of plain [`parse`](crate::parse): aliases in typed values
+    /// only
+    ///
+    /// - `dialect`: `Dialect::Lenient`
+    /// - `allow_non_finite`: true
+    /// - `infer_aliases`: false
+    fn default() -> Self {
+        Self {
+            dialect: Dialect::Lenient,
+            allow_non_finite: true,
+            infer_aliases: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options of plain [`parse`](crate::parse)
+    ///
+    /// Typed values accept every alias, but untyped `yes`, `no` and `n`
+    /// stay strings; use [`ParseOptions::lenient`] to infer those too.
+    /// Non-finite floats are allowed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept canonical spellings only
+    pub fn strict() -> Self {
+        Self::new().dialect(Dialect::Strict)
+    }
+
+    /// Accept every documented alias, for untyped values too
+    pub fn lenient() -> Self {
+        Self::new().dialect(Dialect::Lenient).infer_aliases(true)
+    }
+
+    /// Set the dialect
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Set whether NaN and infinite floats are accepted
+    pub fn allow_non_finite(mut self, allow: bool) -> Self {
+        self.allow_non_finite = allow;
+        self
+    }
+
+    /// Set whether untyped `yes`, `no` and `n` are inferred as booleans and
+    /// null (lenient dialect only)
+    pub fn infer_aliases(mut self, infer: bool) -> Self {
+        self.infer_aliases = infer;
+        self
+    }
 }
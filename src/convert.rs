@@ -0,0 +1,185 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions between Value and Rust types
+//!
+//! `From` builds a `Value` of the matching variant. `TryFrom<&Value>` reads a
+//! primitive back, accepting any integer variant whose value fits the target
+//! width; the range check is the same one `TypeHint::parse_value` applies.
+
+use crate::decimal::Decimal;
+use crate::error::{Error, ErrorKind};
+use crate::map::Map;
+use crate::temporal::{Date, DateTime, Time, Timestamp};
+use crate::types::TypeHint;
+use crate::value::Value;
+use std::collections::HashMap;
+
+macro_rules! from_primitive {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::$variant(n)
+                }
+            }
+        )*
+    };
+}
+
+from_primitive! {
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    f32 => F32,
+    f64 => F64,
+    Decimal => Decimal,
+    bool => Bool,
+    String => Str,
+    Date => Date,
+    Time => Time,
+    DateTime => DateTime,
+    Timestamp => Timestamp,
+    Map => Object,
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(opt: Option<T>) -> Self {
+        opt.map_or(Value::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<HashMap<String, T>> for Value {
+    fn from(map: HashMap<String, T>) -> Self {
+        Value::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+/// Error for a value of the wrong variant
+fn mismatch(expected: &str, value: &Value) -> Error {
+    Error::value(
+        ErrorKind::TypeMismatch,
+        format!("Cannot convert {} to {}", value.type_name(), expected),
+    )
+}
+
+macro_rules! try_from_integer {
+    ($($ty:ty => $hint:ident),* $(,)?) => {
+        $(
+            impl TryFrom<&Value> for $ty {
+                type Error = Error;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    let n = value
+                        .as_integer()
+                        .ok_or_else(|| mismatch(stringify!($ty), value))?;
+                    TypeHint::$hint.check_integer(n)?;
+                    Ok(n as $ty)
+                }
+            }
+        )*
+    };
+}
+
+try_from_integer! {
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+}
+
+impl TryFrom<&Value> for u128 {
+    type Error = Error;
+
+    /// Accepts `U128`, and any other non-negative integer
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        if !value.is_integer() {
+            return Err(mismatch("u128", value));
+        }
+        value.as_u128().ok_or_else(|| {
+            Error::value(
+                ErrorKind::IntegerOutOfRange,
+                format!(
+                    "Integer {} out of range for u128",
+                    value.as_integer().unwrap_or(0)
+                ),
+            )
+        })
+    }
+}
+
+impl TryFrom<&Value> for f32 {
+    type Error = Error;
+
+    /// Accepts `F32`, and `F64` when it converts to `f32` without loss
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::F32(n) => Ok(*n),
+            Value::F64(n) if n.is_nan() || f64::from(*n as f32) == *n => Ok(*n as f32),
+            Value::F64(n) => Err(Error::value(
+                ErrorKind::TypeMismatch,
+                format!("Float {} cannot be represented exactly as f32", n),
+            )),
+            _ => Err(mismatch("f32", value)),
+        }
+    }
+}
+
+impl TryFrom<&Value> for f64 {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.as_f64().ok_or_else(|| mismatch("f64", value))
+    }
+}
+
+impl TryFrom<&Value> for Decimal {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.as_decimal().ok_or_else(|| mismatch("decimal", value))
+    }
+}
+
+impl TryFrom<&Value> for bool {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.as_bool().ok_or_else(|| mismatch("bool", value))
+    }
+}
+
+impl TryFrom<&Value> for String {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| mismatch("string", value))
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! GBLN Exact Decimals
+//!
+//! Values of the `dec:P.S` type hint: a number with at most `P` significant
+//! digits, `S` of them after the decimal point (as SQL `NUMERIC(P, S)`).
+//! Stored as an integer mantissa and a scale, so `12.50` is exactly
+//! `1250 × 10⁻²` with no binary rounding.
+
+use crate::error::{Error, ErrorKind};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest supported precision (digits that fit an `i128` mantissa)
+pub const MAX_PRECISION: u32 = 38;
+
+/// An exact decimal number: `mantissa × 10^-scale`
+///
+/// Equality is structural, so `1.5` and `1.50` are different values (as
+/// they would be in `dec:3.1` and `dec:3.2` columns). Ordering is numeric,
+/// with the scale only breaking ties between equal numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Create a decimal; the mantissa and scale are limited to 38 digits
+    pub fn new(mantissa: i128, scale: u32) -> Result<Self, Error> {
+        if scale > MAX_PRECISION || digit_count(mantissa) > MAX_PRECISION {
+            return Err(Error::value(
+                ErrorKind::ValueOutOfRange,
+                format!(
+                    "Decimal {}e-{} exceeds {} digits",
+                    mantissa, scale, MAX_PRECISION
+                ),
+            ));
+        }
+        Ok(Self { mantissa, scale })
+    }
+
+    /// The unscaled integer value (`1250` for `12.50`)
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Digits after the decimal point
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Significant digits of the mantissa (at least 1)
+    pub fn digits(&self) -> u32 {
+        digit_count(self.mantissa)
+    }
+
+    /// The same number with a different scale
+    ///
+    /// Returns `None` if that would drop non-zero digits or exceed 38 digits.
+    pub fn with_scale(&self, scale: u32) -> Option<Self> {
+        let mantissa = if scale >= self.scale {
+            self.mantissa
+                .checked_mul(10i128.checked_pow(scale - self.scale)?)?
+        } else {
+            let divisor = 10i128.checked_pow(self.scale - scale)?;
+            if self.mantissa % divisor != 0 {
+                return None;
+            }
+            self.mantissa / divisor
+        };
+        Decimal::new(mantissa, scale).ok()
+    }
+
+    /// Nearest `f64` (may round)
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Compare by value alone, so `1.5` and `1.50` are equal
+    pub(crate) fn numeric_cmp(&self, other: &Self) -> Ordering {
+        self.value_key().cmp(&other.value_key())
+    }
+
+    /// Integer part and fraction scaled to 38 digits, ordering by value
+    fn value_key(&self) -> (i128, i128) {
+        let unit = 10i128.pow(self.scale);
+        let fraction = self.mantissa.rem_euclid(unit) * 10i128.pow(MAX_PRECISION - self.scale);
+        (self.mantissa.div_euclid(unit), fraction)
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numeric_cmp(other)
+            .then_with(|| self.scale.cmp(&other.scale))
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    /// Parse `[-]digits[.digits]`; the scale is the number of digits after
+    /// the point (no exponent notation)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || {
+            Error::value(
+                ErrorKind::TypeMismatch,
+                format!("Cannot parse '{}' as decimal", s),
+            )
+        };
+
+        let unsigned = s.strip_prefix('-').unwrap_or(s);
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let is_digits = |t: &str| t.bytes().all(|c| c.is_ascii_digit());
+        if int.is_empty() || !is_digits(int) || !is_digits(frac) || unsigned.ends_with('.') {
+            return Err(malformed());
+        }
+
+        let digits = format!("{}{}", int, frac);
+        let significant = digits.trim_start_matches('0');
+        if significant.len() > MAX_PRECISION as usize || frac.len() > MAX_PRECISION as usize {
+            return Err(Error::value(
+                ErrorKind::ValueOutOfRange,
+                format!("Decimal {} exceeds {} digits", s, MAX_PRECISION),
+            ));
+        }
+        let magnitude = match significant {
+            "" => 0,
+            _ => significant.parse::<i128>().map_err(|_| malformed())?,
+        };
+        let mantissa = if s.starts_with('-') {
+            -magnitude
+        } else {
+            magnitude
+        };
+
+        Decimal::new(mantissa, frac.len() as u32)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let padded = format!("{:0>width$}", digits, width = scale + 1);
+        let (int, frac) = padded.split_at(padded.len() - scale);
+        write!(f, "{}{}.{}", sign, int, frac)
+    }
+}
+
+/// Number of decimal digits of `|n|` (1 for zero)
+fn digit_count(n: i128) -> u32 {
+    n.unsigned_abs().checked_ilog10().map_or(1, |d| d + 1)
+}
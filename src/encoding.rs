@@ -0,0 +1,87 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Textual encodings for binary values
+//!
+//! `bin` values are written as standard base64 (RFC 4648, padded). Input may
+//! omit the padding, or use hex with a `hex:` prefix instead.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as padded standard base64
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+/// Decode `bin` content: `hex:` followed by hex digits, or base64
+pub(crate) fn decode_binary(s: &str) -> Result<Vec<u8>, String> {
+    match s.strip_prefix("hex:") {
+        Some(hex) => decode_hex(hex),
+        None => decode_base64(s),
+    }
+}
+
+/// Decode standard base64, with or without padding
+///
+/// Rejects characters outside the alphabet and encodings whose unused
+/// trailing bits are not zero, so every byte string has exactly one
+/// (padded) encoding.
+fn decode_base64(s: &str) -> Result<Vec<u8>, String> {
+    let invalid = || format!("Invalid base64: '{}'", s);
+
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+    if padding > 2 || (padding > 0 && !s.len().is_multiple_of(4)) || data.len() % 4 == 1 {
+        return Err(invalid());
+    }
+
+    let mut out = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in data.bytes() {
+        let value = ALPHABET.iter().position(|&a| a == c).ok_or_else(invalid)?;
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    if buffer != 0 {
+        return Err(invalid());
+    }
+    Ok(out)
+}
+
+/// Decode hex digits (either case)
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid hex: '{}'", s));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
@@ -23,6 +23,7 @@ pub enum ErrorKind {
     // Lexer errors
     UnexpectedCharacter,
     UnterminatedString,
+    InvalidEscape,
 
     // Parser errors
     UnexpectedToken,
@@ -31,12 +32,24 @@ pub enum ErrorKind {
 
     // Type errors
     IntegerOutOfRange,
+    ValueOutOfRange,
     StringTooLong,
+    BytesTooLong,
+    UnknownVariant,
+    PatternMismatch,
+    NonFiniteFloat,
     TypeMismatch,
     InvalidTypeHint,
 
     // Structural errors
     DuplicateKey,
+    ColumnCountMismatch,
+    TooManyItems,
+    TooFewItems,
+
+    // Path lookup errors
+    InvalidPath,
+    PathNotFound,
 
     // I/O errors
     IoError,
@@ -68,6 +81,30 @@ impl Error {
             suggestion: None,
         }
     }
+
+    /// Create a value validation error (no position until placed with
+    /// [`Error::at`])
+    pub fn value(kind: ErrorKind, message: String) -> Self {
+        Self::new(kind, 0, 0, message)
+    }
+
+    /// Set the line and column of the error
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = line;
+        self.column = column;
+        self
+    }
+
+    /// Create a path lookup error (column is the position within the path)
+    pub fn path(kind: ErrorKind, column: usize, message: String) -> Self {
+        Self {
+            kind,
+            line: 0,
+            column,
+            message,
+            suggestion: None,
+        }
+    }
 }
 
 impl From<String> for Error {
@@ -0,0 +1,164 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Escape sequences in parenthesised values
+//!
+//! Between `(` and `)` a backslash starts an escape:
+//!
+//! - `\\` backslash
+//! - `\(` and `\)` parentheses
+//! - `\"` double quote (needed in quoted keys)
+//! - `\n`, `\r`, `\t` newline, carriage return, tab
+//! - `\u{1F600}` any character by its hex code point
+//!
+//! A backslash before any other character is kept as written, so values
+//! such as `C:\Users` read the same as before escapes existed. Everything
+//! else is taken literally, including leading and trailing whitespace.
+//! Unescaped parentheses must be balanced.
+//!
+//! Quoted keys (`"first name"{...}`) use the same escapes.
+
+use crate::error::{Error, ErrorKind};
+use std::borrow::Cow;
+
+/// Resolve escape sequences in raw value content
+///
+/// Borrows `raw` unless it contains a backslash.
+pub(crate) fn unescape(raw: &str) -> Result<Cow<'_, str>, Error> {
+    if !raw.contains('\\') {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some(c @ ('\\' | '(' | ')' | '"')) => c,
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') if chars.as_str().starts_with('{') => unescape_code_point(&mut chars)?,
+            // Not an escape: keep the backslash
+            Some(c) => {
+                out.push('\\');
+                c
+            }
+            None => '\\',
+        };
+        out.push(escaped);
+    }
+
+    Ok(Cow::Owned(out))
+}
+
+/// Read the `{hex}` part of a `\u{...}` escape
+fn unescape_code_point(chars: &mut std::str::Chars<'_>) -> Result<char, Error> {
+    let rest = chars.as_str();
+    let code = rest
+        .strip_prefix('{')
+        .and_then(|r| r.split_once('}'))
+        .map(|(hex, _)| hex);
+
+    let ch = code
+        .filter(|hex| (1..=6).contains(&hex.len()))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .and_then(char::from_u32);
+
+    match (code, ch) {
+        (Some(hex), Some(ch)) => {
+            // Skip `{hex}`
+            *chars = rest[hex.len() + 2..].chars();
+            Ok(ch)
+        }
+        _ => Err(Error::value(
+            ErrorKind::InvalidEscape,
+            format!(
+                "Invalid escape sequence '\\u{}' (expected \\u{{hex code point}})",
+                code.map(|hex| format!("{{{}}}", hex)).unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+/// Escape text for writing between `(` and `)`
+///
+/// Backslashes, unbalanced parentheses and control characters are escaped;
+/// everything else, including balanced parentheses, is kept as is.
+pub(crate) fn escape(s: &str) -> Cow<'_, str> {
+    let unbalanced = unbalanced_parens(s);
+    let needs_escape =
+        |i: usize, c: char| c == '\\' || c.is_control() || unbalanced.binary_search(&i).is_ok();
+
+    if !s.char_indices().any(|(i, c)| needs_escape(i, c)) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut out = String::with_capacity(s.len() + 8);
+    for (i, c) in s.char_indices() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c if needs_escape(i, c) => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Key text of a quoted key token (`"first name"` gives `first name`)
+pub(crate) fn unquote(raw: &str) -> Result<Cow<'_, str>, Error> {
+    let inner = raw
+        .strip_prefix('"')
+        .and_then(|r| r.strip_suffix('"'))
+        .unwrap_or(raw);
+    unescape(inner)
+}
+
+/// Write a key in quotes, escaping backslashes, quotes and control
+/// characters
+pub(crate) fn quote(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 2);
+    out.push('"');
+    for c in key.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Byte offsets of parentheses without a partner, in ascending order
+fn unbalanced_parens(s: &str) -> Vec<usize> {
+    let mut open = Vec::new();
+    let mut unbalanced = Vec::new();
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => open.push(i),
+            ')' if open.pop().is_none() => unbalanced.push(i),
+            _ => {}
+        }
+    }
+
+    unbalanced.extend(open);
+    unbalanced.sort_unstable();
+    unbalanced
+}
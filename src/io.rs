@@ -7,8 +7,8 @@
 //! including XZ-compressed I/O format.
 
 use crate::{Error, GblnConfig, Value};
-use std::fs;
-use std::io::{Read, Write};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 use xz2::read::XzDecoder;
 use xz2::write::XzEncoder;
@@ -45,16 +45,13 @@ use xz2::write::XzEncoder;
 /// # Errors
 ///
 /// Returns an error if:
-/// - Serialisation fails
+/// - Serialisation fails (e.g. a non-finite float when `allow_non_finite`
+///   is false)
 /// - Compression fails
 /// - File cannot be written
 pub fn write_io(value: &Value, path: &Path, config: &GblnConfig) -> Result<(), Error> {
-    // 1. Serialise based on mini_mode
-    let content = if config.mini_mode {
-        crate::to_string(value)
-    } else {
-        crate::to_string_pretty(value)
-    };
+    // 1. Serialise based on mini_mode, indent and sort_keys
+    let content = crate::to_string_with_config(value, config)?;
 
     // 2. Optionally compress
     let bytes = if config.compress {
@@ -74,6 +71,10 @@ pub fn write_io(value: &Value, path: &Path, config: &GblnConfig) -> Result<(), E
 /// This function reads a file and automatically detects if it's XZ compressed.
 /// The content is then parsed into a GBLN value.
 ///
+/// The file is streamed through the decompressor straight into the parser
+/// (see [`parse_reader`](crate::parse_reader)), so neither the compressed nor
+/// the decompressed text is held in memory as a whole.
+///
 /// # Auto-Detection
 ///
 /// The function checks for XZ magic bytes (`FD 37 7A 58 5A 00`) and automatically
@@ -101,19 +102,19 @@ pub fn write_io(value: &Value, path: &Path, config: &GblnConfig) -> Result<(), E
 /// - Content is not valid UTF-8
 /// - Parsing fails
 pub fn read_io(path: &Path) -> Result<Value, Error> {
-    // 1. Read file
-    let bytes = fs::read(path).map_err(|e| Error::io(e.to_string()))?;
+    // 1. Open file
+    let file = File::open(path).map_err(|e| Error::io(e.to_string()))?;
+    let mut reader = BufReader::new(file);
 
-    // 2. Decompress if needed
-    let text = if is_xz_compressed(&bytes) {
-        let decompressed = decompress_xz(&bytes)?;
-        String::from_utf8(decompressed).map_err(|e| Error::io(e.to_string()))?
-    } else {
-        String::from_utf8(bytes).map_err(|e| Error::io(e.to_string()))?
-    };
+    // 2. Peek at the magic bytes
+    let compressed = is_xz_compressed(reader.fill_buf().map_err(|e| Error::io(e.to_string()))?);
 
-    // 3. Parse
-    crate::parse(&text)
+    // 3. Parse, decompressing on the fly if needed
+    if compressed {
+        crate::parse_reader(BufReader::new(XzDecoder::new(reader)))
+    } else {
+        crate::parse_reader(reader)
+    }
 }
 
 /// Compress data using XZ compression
@@ -136,24 +137,6 @@ fn compress_xz(data: &[u8], level: u8) -> Result<Vec<u8>, Error> {
         .map_err(|e| Error::io(format!("XZ compression failed: {}", e)))
 }
 
-/// Decompress XZ-compressed data
-///
-/// # Arguments
-///
-/// * `data` - XZ-compressed data
-///
-/// # Errors
-///
-/// Returns an error if decompression fails
-fn decompress_xz(data: &[u8]) -> Result<Vec<u8>, Error> {
-    let mut decoder = XzDecoder::new(data);
-    let mut result = Vec::new();
-    decoder
-        .read_to_end(&mut result)
-        .map_err(|e| Error::io(format!("XZ decompression failed: {}", e)))?;
-    Ok(result)
-}
-
 /// Check if data is XZ compressed
 ///
 /// XZ files start with magic bytes: `FD 37 7A 58 5A 00`
@@ -4,17 +4,63 @@
 //! GBLN Lexer
 //!
 //! Tokenizes GBLN input into a stream of tokens.
+//!
+//! The parser pulls tokens with [`Lexer::next_token`] and reads value and
+//! type hint content raw. Tooling can instead iterate over the lexer to get
+//! [`Spanned`] tokens with their position; with [`Lexer::trivia`] and
+//! [`Lexer::content`] enabled the stream is lossless, so concatenating the
+//! tokens reproduces the input exactly.
+//!
+//! ```
+//! use gbln::{Lexer, Token};
+//!
+//! let input = "user{name<s32>(Alice) :| comment\n}";
+//! let tokens: Vec<_> = Lexer::new(input)
+//!     .trivia(true)
+//!     .content(true)
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(tokens[4].value, Token::Type("s32".into()));
+//! assert_eq!(tokens[7].value, Token::Content("Alice".into()));
+//! assert_eq!(tokens[7].span.range(), 15..20);
+//!
+//! let text: String = tokens.iter().map(|t| t.value.as_str()).collect();
+//! assert_eq!(text, input);
+//! ```
 
 use crate::error::{Error, ErrorKind};
+use crate::utf8::Utf8Reader;
+use std::borrow::Cow;
+use std::io::BufRead;
+use std::ops::Range;
 
 /// A token in the GBLN input stream
+///
+/// Text tokens borrow from the input where possible.
 #[derive(Debug, Clone, PartialEq)]
-pub enum Token {
+pub enum Token<'a> {
     /// Object key (e.g., "user", "name", "age")
-    Key(String),
+    Key(Cow<'a, str>),
+
+    /// Quoted object key as written, quotes and escapes included
+    /// (e.g., `"first name"`)
+    QuotedKey(Cow<'a, str>),
 
-    /// Type hint (e.g., "u32", "s64", "b")
-    Type(String),
+    /// Raw type hint between < and > (e.g., "u32", "s64?"), only produced
+    /// when iterating with [`Lexer::content`]
+    Type(Cow<'a, str>),
+
+    /// Raw value between ( and ), escapes unresolved, only produced when
+    /// iterating with [`Lexer::content`]
+    Content(Cow<'a, str>),
+
+    /// Run of whitespace, only produced when iterating with [`Lexer::trivia`]
+    Whitespace(Cow<'a, str>),
+
+    /// `:|` comment up to the end of the line, doc comments (`:||`)
+    /// included, only produced when iterating with [`Lexer::trivia`]
+    Comment(Cow<'a, str>),
 
     /// Left parenthesis (
     LParen,
@@ -44,27 +90,284 @@ pub enum Token {
     Eof,
 }
 
+impl Token<'_> {
+    /// Source text of the token (empty for [`Token::Eof`])
+    pub fn as_str(&self) -> &str {
+        match self {
+            Token::Key(s)
+            | Token::QuotedKey(s)
+            | Token::Type(s)
+            | Token::Content(s)
+            | Token::Whitespace(s)
+            | Token::Comment(s) => s,
+            Token::LParen => "(",
+            Token::RParen => ")",
+            Token::LBrace => "{",
+            Token::RBrace => "}",
+            Token::LBracket => "[",
+            Token::RBracket => "]",
+            Token::LAngle => "<",
+            Token::RAngle => ">",
+            Token::Eof => "",
+        }
+    }
+
+    /// Check if the token is whitespace or a comment
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, Token::Whitespace(_) | Token::Comment(_))
+    }
+}
+
+/// Location of a token in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the first character
+    pub start: usize,
+
+    /// Byte offset just past the last character
+    pub end: usize,
+
+    /// Line of the first character (1-based)
+    pub line: usize,
+
+    /// Column of the first character (1-based, counted in characters)
+    pub column: usize,
+}
+
+impl Span {
+    /// Byte range of the token, for slicing the input
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// A value with the location it was read from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+/// Raw content the iterator reads next, after `(` or `<`
+#[derive(Debug, Clone, Copy)]
+enum Raw {
+    Value,
+    Hint,
+}
+
+/// Where the lexer reads its input from
+enum Source<'a> {
+    /// Whole input in memory; tokens borrow from it
+    Str(&'a str),
+    /// Streamed input; tokens own their text
+    Reader(Utf8Reader<'a>),
+}
+
 /// Lexer for tokenizing GBLN input
 pub struct Lexer<'a> {
-    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    source: Source<'a>,
     position: usize,
     line: usize,
     column: usize,
+    trivia: bool,
+    content: bool,
+    raw: Option<Raw>,
+    pending: Option<Spanned<Token<'a>>>,
+    done: bool,
+    /// Doc comment lines read since the last token
+    doc: Vec<String>,
 }
 
 impl<'a> Lexer<'a> {
     /// Create a new lexer for the given input
     pub fn new(input: &'a str) -> Self {
+        Self::with_source(Source::Str(input))
+    }
+
+    /// Create a lexer that reads and decodes UTF-8 input incrementally
+    ///
+    /// Only the text of the current token is held in memory, so tokens own
+    /// their text instead of borrowing it. Read errors and invalid UTF-8
+    /// are reported as [`ErrorKind::IoError`] at the position where the
+    /// input stops.
+    pub fn from_reader<R: BufRead + 'a>(reader: R) -> Self {
+        Self::with_source(Source::Reader(Utf8Reader::new(reader)))
+    }
+
+    fn with_source(source: Source<'a>) -> Self {
         Self {
-            chars: input.char_indices().peekable(),
+            source,
             position: 0,
             line: 1,
             column: 1,
+            trivia: false,
+            content: false,
+            raw: None,
+            pending: None,
+            done: false,
+            doc: Vec::new(),
+        }
+    }
+
+    /// Yield whitespace and comments as tokens when iterating
+    pub fn trivia(mut self, enabled: bool) -> Self {
+        self.trivia = enabled;
+        self
+    }
+
+    /// Yield the raw content of `<...>` and `(...)` as [`Token::Type`] and
+    /// [`Token::Content`] when iterating, instead of splitting it into keys
+    pub fn content(mut self, enabled: bool) -> Self {
+        self.content = enabled;
+        self
+    }
+
+    /// Get the next token with its location
+    ///
+    /// Honours [`trivia`](Lexer::trivia) and [`content`](Lexer::content);
+    /// returns [`Token::Eof`] at the end of the input.
+    pub fn next_spanned(&mut self) -> Result<Spanned<Token<'a>>, Error> {
+        if let Some(token) = self.pending.take() {
+            return Ok(token);
+        }
+        if let Some(raw) = self.raw.take() {
+            return self.read_raw(raw);
+        }
+
+        if self.trivia {
+            self.release();
+            let token = self.read_trivia();
+            if let Some(token) = self.checked(token)? {
+                return Ok(token);
+            }
+        } else {
+            self.skip_whitespace_and_comments();
+        }
+
+        let (start, line, column) = (self.position, self.line, self.column);
+        let token = self.next_token()?;
+
+        if self.content {
+            match token {
+                Token::LParen => self.raw = Some(Raw::Value),
+                Token::LAngle => self.raw = Some(Raw::Hint),
+                _ => {}
+            }
+        }
+
+        Ok(self.spanned(token, start, line, column))
+    }
+
+    /// Wrap a token that started at `start` and ends at the current position
+    fn spanned(
+        &self,
+        value: Token<'a>,
+        start: usize,
+        line: usize,
+        column: usize,
+    ) -> Spanned<Token<'a>> {
+        Spanned {
+            value,
+            span: Span {
+                start,
+                end: self.position,
+                line,
+                column,
+            },
+        }
+    }
+
+    /// Read a whitespace run or comment, if one starts here
+    fn read_trivia(&mut self) -> Result<Option<Spanned<Token<'a>>>, Error> {
+        let (start, line, column) = (self.position, self.line, self.column);
+
+        match self.peek_char() {
+            Some(' ' | '\t' | '\n' | '\r') => {
+                while let Some(' ' | '\t' | '\n' | '\r') = self.peek_char() {
+                    self.advance();
+                }
+                let text = self.text(start);
+                Ok(Some(self.spanned(
+                    Token::Whitespace(text),
+                    start,
+                    line,
+                    column,
+                )))
+            }
+            Some(':') if self.peek_second() == Some('|') => {
+                while let Some(ch) = self.peek_char() {
+                    if ch == '\n' {
+                        break;
+                    }
+                    self.advance();
+                }
+                let text = self.text(start);
+                Ok(Some(self.spanned(
+                    Token::Comment(text),
+                    start,
+                    line,
+                    column,
+                )))
+            }
+            Some(':') => Err(Error::new(
+                ErrorKind::UnexpectedCharacter,
+                line,
+                column,
+                "Unexpected character: ':'".to_string(),
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Read raw content after `(` or `<` and queue the closing delimiter
+    fn read_raw(&mut self, raw: Raw) -> Result<Spanned<Token<'a>>, Error> {
+        let (start, line, column) = (self.position, self.line, self.column);
+        let (text, close) = match raw {
+            Raw::Value => (self.read_parenthesized_content()?, Token::RParen),
+            Raw::Hint => (self.read_type_hint_content()?, Token::RAngle),
+        };
+
+        // The closing delimiter is never a newline, so it sits just before
+        // the current position on the current line
+        let close = Spanned {
+            value: close,
+            span: Span {
+                start: self.position - 1,
+                end: self.position,
+                line: self.line,
+                column: self.column - 1,
+            },
+        };
+        if text.is_empty() {
+            return Ok(close);
         }
+
+        let end = start + text.len();
+        let token = match raw {
+            Raw::Value => Token::Content(text),
+            Raw::Hint => Token::Type(text),
+        };
+        self.pending = Some(close);
+        Ok(Spanned {
+            value: token,
+            span: Span {
+                start,
+                end,
+                line,
+                column,
+            },
+        })
     }
 
     /// Get the next token from the input
-    pub fn next_token(&mut self) -> Result<Token, Error> {
+    pub fn next_token(&mut self) -> Result<Token<'a>, Error> {
+        self.doc.clear();
+        self.release();
+        let token = self.read_token();
+        self.checked(token)
+    }
+
+    fn read_token(&mut self) -> Result<Token<'a>, Error> {
         self.skip_whitespace_and_comments();
 
         match self.peek_char() {
@@ -106,6 +409,7 @@ impl<'a> Lexer<'a> {
                 // Since we now allow hyphens in read_identifier(), just treat it like any identifier start
                 self.read_identifier()
             }
+            Some('"') => self.read_quoted_key(),
             Some(ch) if is_identifier_start(ch) || ch.is_ascii_digit() => self.read_identifier(),
             Some(ch) => Err(Error::new(
                 ErrorKind::UnexpectedCharacter,
@@ -118,13 +422,64 @@ impl<'a> Lexer<'a> {
 
     /// Peek at the current character without consuming it
     fn peek_char(&mut self) -> Option<char> {
-        self.chars.peek().map(|(_, ch)| *ch)
+        self.char_at(self.position)
+    }
+
+    /// Peek at the character after an ASCII current character
+    fn peek_second(&mut self) -> Option<char> {
+        self.char_at(self.position + 1)
+    }
+
+    fn char_at(&mut self, pos: usize) -> Option<char> {
+        match &mut self.source {
+            Source::Str(input) => input.get(pos..)?.chars().next(),
+            Source::Reader(reader) => reader.char_at(pos),
+        }
+    }
+
+    /// Text read since byte offset `start`
+    fn consumed(&self, start: usize) -> &str {
+        match &self.source {
+            Source::Str(input) => &input[start..self.position],
+            Source::Reader(reader) => reader.slice(start, self.position),
+        }
+    }
+
+    /// Token text read since byte offset `start`, borrowed from in-memory
+    /// input
+    fn text(&self, start: usize) -> Cow<'a, str> {
+        match self.source {
+            Source::Str(input) => Cow::Borrowed(&input[start..self.position]),
+            Source::Reader(ref reader) => {
+                Cow::Owned(reader.slice(start, self.position).to_string())
+            }
+        }
+    }
+
+    /// Let a streamed source drop the text before the current position
+    fn release(&mut self) {
+        if let Source::Reader(reader) = &mut self.source {
+            reader.release(self.position);
+        }
+    }
+
+    /// Report a read or decoding error that cut the input short instead of
+    /// the result read from the truncated input
+    fn checked<T>(&mut self, result: Result<T, Error>) -> Result<T, Error> {
+        let error = match &mut self.source {
+            Source::Reader(reader) => reader.take_error(),
+            Source::Str(_) => None,
+        };
+        match error {
+            Some(e) => Err(e.at(self.line, self.column)),
+            None => result,
+        }
     }
 
     /// Advance to the next character
     fn advance(&mut self) -> Option<char> {
-        if let Some((pos, ch)) = self.chars.next() {
-            self.position = pos + ch.len_utf8();
+        if let Some(ch) = self.peek_char() {
+            self.position += ch.len_utf8();
 
             if ch == '\n' {
                 self.line += 1;
@@ -153,12 +508,17 @@ impl<'a> Lexer<'a> {
                     if self.peek_char() == Some('|') {
                         // This is a comment, skip until newline
                         self.advance(); // consume '|'
+                        let start = self.position;
                         while let Some(ch) = self.peek_char() {
                             if ch == '\n' {
                                 break;
                             }
                             self.advance();
                         }
+                        if let Some(line) = doc_line(self.consumed(start)) {
+                            let line = line.to_string();
+                            self.doc.push(line);
+                        }
                     } else {
                         // Not a comment, ':' alone is an error
                         break;
@@ -169,33 +529,79 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    /// Read a key, identifier, or value
-    fn read_identifier(&mut self) -> Result<Token, Error> {
-        let mut ident = String::new();
+    /// Read a key, identifier, or value (borrowed from the input)
+    fn read_identifier(&mut self) -> Result<Token<'a>, Error> {
+        let start = self.position;
 
         // First character already validated by caller
-        if let Some(ch) = self.advance() {
-            ident.push(ch);
-        }
+        self.advance();
 
         // Read remaining characters (alphanumeric + underscore + hyphen + optional decimal point for floats)
         let mut has_dot = false;
         while let Some(ch) = self.peek_char() {
-            if ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' {
-                ident.push(ch);
+            if ch.is_alphanumeric() || ch == '_' || ch == '-' {
                 self.advance();
-            } else if ch == '.' && !has_dot && ident.chars().all(|c| c.is_ascii_digit() || c == '-')
+            } else if ch == '.'
+                && !has_dot
+                && self
+                    .consumed(start)
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '-')
             {
                 // Allow one decimal point in numeric tokens for float support
                 has_dot = true;
-                ident.push(ch);
                 self.advance();
             } else {
                 break;
             }
         }
 
-        Ok(Token::Key(ident))
+        Ok(Token::Key(self.text(start)))
+    }
+
+    /// Read a quoted key: `"..."`, where a backslash escapes the next
+    /// character (borrowed from the input, quotes included)
+    fn read_quoted_key(&mut self) -> Result<Token<'a>, Error> {
+        let start = self.position;
+        let (line, column) = (self.line, self.column);
+        self.advance();
+
+        loop {
+            match self.peek_char() {
+                None | Some('\n') => {
+                    return Err(Error::new(
+                        ErrorKind::UnterminatedString,
+                        line,
+                        column,
+                        "Unterminated quoted key".to_string(),
+                    ))
+                }
+                Some('"') => {
+                    self.advance();
+                    return Ok(Token::QuotedKey(self.text(start)));
+                }
+                Some('\\') => {
+                    self.advance();
+                    if self.peek_char().is_some_and(|ch| ch != '\n') {
+                        self.advance();
+                    }
+                }
+                Some(_) => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    /// Take the doc comment written right before the current token, its
+    /// lines joined with `\n`
+    pub(crate) fn take_doc(&mut self) -> Option<String> {
+        if self.doc.is_empty() {
+            return None;
+        }
+        let doc = self.doc.join("\n");
+        self.doc.clear();
+        Some(doc)
     }
 
     /// Get current line number (for error reporting)
@@ -208,11 +614,103 @@ impl<'a> Lexer<'a> {
         self.column
     }
 
+    /// Read raw type hint content until the matching closing angle bracket
+    /// Used for reading hints between < and >, which may contain characters
+    /// that are not tokens on their own (e.g. `u32?`)
+    /// Consumes the closing > as well
+    /// Returns the hint text (without the closing angle bracket)
+    pub fn read_type_hint_content(&mut self) -> Result<Cow<'a, str>, Error> {
+        self.release();
+        let content = self.read_hint_text();
+        self.checked(content)
+    }
+
+    fn read_hint_text(&mut self) -> Result<Cow<'a, str>, Error> {
+        let start = self.position;
+        let mut depth = 0;
+
+        loop {
+            match self.peek_char() {
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        self.line,
+                        self.column,
+                        "Unexpected end of input while reading type hint".to_string(),
+                    ))
+                }
+                Some('<') => {
+                    depth += 1;
+                    self.advance();
+                }
+                Some('>') => {
+                    if depth == 0 {
+                        let content = self.text(start);
+                        self.advance();
+                        return Ok(content);
+                    }
+                    depth -= 1;
+                    self.advance();
+                }
+                // A `/regex/` after `:` may contain `<` and `>`
+                Some('/') if self.consumed(start).ends_with(':') => {
+                    self.skip_pattern()?;
+                }
+                // So may a quoted column name in a table schema
+                Some('"') => {
+                    self.read_quoted_key()?;
+                }
+                Some(_) => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    /// Skip a `/regex/` in a type hint; `\` escapes the next character
+    fn skip_pattern(&mut self) -> Result<(), Error> {
+        self.advance();
+        loop {
+            match self.peek_char() {
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        self.line,
+                        self.column,
+                        "Unexpected end of input while reading pattern".to_string(),
+                    ))
+                }
+                Some('/') => {
+                    self.advance();
+                    return Ok(());
+                }
+                Some('\\') => {
+                    self.advance();
+                    if self.peek_char().is_some() {
+                        self.advance();
+                    }
+                }
+                Some(_) => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
     /// Read raw content until matching closing parenthesis
     /// Used for reading values between ( and )
+    /// A backslash escapes the next character, so `\)` does not close
     /// Consumes the closing ) as well
-    pub fn read_parenthesized_content(&mut self) -> Result<String, Error> {
-        let mut content = String::new();
+    /// Returns the value text (without the closing paren), with escape
+    /// sequences still in place
+    pub fn read_parenthesized_content(&mut self) -> Result<Cow<'a, str>, Error> {
+        self.release();
+        let content = self.read_value_text();
+        self.checked(content)
+    }
+
+    fn read_value_text(&mut self) -> Result<Cow<'a, str>, Error> {
+        let start = self.position;
         let mut depth = 0;
 
         loop {
@@ -227,21 +725,25 @@ impl<'a> Lexer<'a> {
                 }
                 Some('(') => {
                     depth += 1;
-                    content.push('(');
                     self.advance();
                 }
                 Some(')') => {
                     if depth == 0 {
                         // Found matching closing paren - consume it
+                        let content = self.text(start);
                         self.advance();
                         return Ok(content);
                     }
                     depth -= 1;
-                    content.push(')');
                     self.advance();
                 }
-                Some(ch) => {
-                    content.push(ch);
+                Some('\\') => {
+                    self.advance();
+                    if self.peek_char().is_some() {
+                        self.advance();
+                    }
+                }
+                Some(_) => {
                     self.advance();
                 }
             }
@@ -249,7 +751,71 @@ impl<'a> Lexer<'a> {
     }
 }
 
-/// Check if character can start an identifier
+/// Text of a doc comment line, given what follows `:|`
+///
+/// `:|| text` is a doc comment; `:|||` and longer runs are not, so that
+/// separator lines stay ordinary comments.
+fn doc_line(comment: &str) -> Option<&str> {
+    let text = comment.strip_prefix('|').filter(|t| !t.starts_with('|'))?;
+    let text = text.strip_suffix('\r').unwrap_or(text);
+    Some(text.strip_prefix(' ').unwrap_or(text))
+}
+
+/// Check if character can start an identifier (any Unicode letter or `_`)
 fn is_identifier_start(ch: char) -> bool {
-    ch.is_ascii_alphabetic() || ch == '_'
+    ch.is_alphabetic() || ch == '_'
+}
+
+/// Check that `text` reads as a single [`Token::Key`], as a bare key or
+/// array element
+///
+/// Mirrors `read_identifier`: a letter, `_`, ASCII digit or `-`, then
+/// letters, digits, `_` and `-`, with one `.` allowed after a run of ASCII
+/// digits and `-`.
+pub(crate) fn is_bare_token(text: &str) -> bool {
+    let mut chars = text.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if !(is_identifier_start(first) || first.is_ascii_digit() || first == '-') {
+        return false;
+    }
+
+    let mut numeric = first.is_ascii_digit() || first == '-';
+    let mut has_dot = false;
+    for ch in chars {
+        if ch == '.' && numeric && !has_dot {
+            has_dot = true;
+        } else if ch.is_alphanumeric() || ch == '_' || ch == '-' {
+            numeric &= ch.is_ascii_digit() || ch == '-';
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Spanned<Token<'a>>, Error>;
+
+    /// Next token with its location; ends after [`Token::Eof`] or the
+    /// first error
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_spanned() {
+            Ok(Spanned {
+                value: Token::Eof, ..
+            }) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+            token => Some(token),
+        }
+    }
 }
@@ -5,22 +5,43 @@
 //!
 //! A type-safe, LLM-native data serialisation format with parse-time validation.
 
+pub mod borrowed;
+mod cmp;
 pub mod config;
+mod convert;
+pub mod decimal;
+mod encoding;
 pub mod error;
+mod escape;
 #[cfg(feature = "compression")]
 pub mod io;
 pub mod lexer;
+pub mod map;
 pub mod parser;
+pub mod path;
+pub mod pattern;
 pub mod serializer;
+pub mod temporal;
 pub mod types;
+mod utf8;
 pub mod value;
+pub mod visit;
 
-pub use config::GblnConfig;
+pub use config::{Dialect, GblnConfig, ParseOptions};
+pub use decimal::Decimal;
 pub use error::{Error, ErrorKind};
 #[cfg(feature = "compression")]
 pub use io::{read_io, write_io};
-pub use lexer::{Lexer, Token};
-pub use parser::parse;
-pub use serializer::{to_string, to_string_pretty};
-pub use types::TypeHint;
-pub use value::Value;
+pub use lexer::{Lexer, Span, Spanned, Token};
+pub use map::{FieldMeta, Map};
+pub use parser::{
+    parse, parse_borrowed, parse_borrowed_with_options, parse_reader, parse_reader_with_options,
+    parse_with_options,
+};
+pub use path::{PathSegment, ValuePath};
+pub use pattern::Pattern;
+pub use serializer::{to_string, to_string_pretty, to_string_with_config};
+pub use temporal::{Date, DateTime, Time, Timestamp};
+pub use types::{Declaration, LenRange, NumRange, StrConstraint, TypeHint};
+pub use value::{Value, ValueIndex};
+pub use visit::{Visit, Visitor, VisitorMut};
@@ -0,0 +1,723 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! GBLN Object Map
+//!
+//! An insertion-ordered map used as the backing store of `Value::Object`.
+//! Fields keep the order in which they appear in the source document, so a
+//! parse → serialise cycle does not reorder hand-written files.
+
+use crate::types::Declaration;
+use crate::value::Value;
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+/// Insertion-ordered map of object fields
+///
+/// Fields are stored in a vector in insertion order, each with the hash of
+/// its key. Lookups go through an open-addressing table of positions in
+/// that vector, so keys are stored once and never re-hashed when the table
+/// grows or the fields are reordered. Iteration follows insertion order.
+/// Equality compares keys and values regardless of order, so two objects
+/// with the same fields in a different order are equal.
+///
+/// Lookup and insertion take constant time on average. Removal keeps the
+/// order of the remaining fields, so like [`Vec::remove`] it takes time
+/// proportional to the number of fields after the removed one.
+///
+/// Each field also carries [`FieldMeta`] describing how it was written in
+/// the source. Metadata is ignored by equality.
+#[derive(Clone)]
+pub struct Map<K = String, V = Value> {
+    entries: Vec<Bucket<K, V>>,
+    /// Positions in `entries`, placed by key hash with linear probing;
+    /// empty or a power of two long
+    table: Vec<usize>,
+    hasher: RandomState,
+}
+
+/// Marks an unused slot of the table
+const EMPTY: usize = usize::MAX;
+
+/// Source-level information about an object field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMeta {
+    /// How the field was declared (`None` for fields built in code)
+    pub declared: Option<Declaration>,
+
+    /// Doc comment written before the field (`:|| ...` lines joined with
+    /// `\n`)
+    pub doc: Option<String>,
+}
+
+#[derive(Clone)]
+struct Bucket<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+    meta: FieldMeta,
+}
+
+/// Table length for `n` fields, keeping the table at most 3/4 full
+fn table_len(n: usize) -> usize {
+    if n == 0 {
+        0
+    } else {
+        (n * 4 / 3 + 1).next_power_of_two().max(8)
+    }
+}
+
+impl<K, V> Map<K, V> {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            table: Vec::new(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Create an empty map with room for `capacity` fields
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            table: vec![EMPTY; table_len(capacity)],
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Number of fields
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the map has no fields
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over fields in insertion order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Iterate mutably over fields in insertion order
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            inner: self.entries.iter_mut(),
+        }
+    }
+
+    /// Iterate over keys in insertion order
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+        self.entries.iter().map(|b| &b.key)
+    }
+
+    /// Iterate over values in insertion order
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + '_ {
+        self.entries.iter().map(|b| &b.value)
+    }
+
+    /// Iterate mutably over values in insertion order
+    pub fn values_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator + '_ {
+        self.entries.iter_mut().map(|b| &mut b.value)
+    }
+
+    /// Iterate over fields with their metadata in insertion order
+    pub fn fields(&self) -> impl DoubleEndedIterator<Item = (&K, &V, &FieldMeta)> + '_ {
+        self.entries.iter().map(|b| (&b.key, &b.value, &b.meta))
+    }
+
+    /// Consume the map into fields with their metadata in insertion order
+    pub fn into_fields(self) -> impl DoubleEndedIterator<Item = (K, V, FieldMeta)> {
+        self.entries.into_iter().map(|b| (b.key, b.value, b.meta))
+    }
+
+    /// Convert keys and values in place of a rebuild, keeping the stored
+    /// hashes and table
+    ///
+    /// Each converted key must hash and compare like the original (as a
+    /// `Cow<str>` and the `String` it holds do), or lookups will miss it.
+    pub(crate) fn convert<K2, V2>(
+        self,
+        mut key: impl FnMut(K) -> K2,
+        mut value: impl FnMut(V) -> V2,
+    ) -> Map<K2, V2> {
+        Map {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|b| Bucket {
+                    hash: b.hash,
+                    key: key(b.key),
+                    value: value(b.value),
+                    meta: b.meta,
+                })
+                .collect(),
+            table: self.table,
+            hasher: self.hasher,
+        }
+    }
+
+    /// Remove all fields
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.table.fill(EMPTY);
+    }
+
+    /// Slot of the table holding position `index`
+    fn slot_of(&self, index: usize) -> usize {
+        let mask = self.table.len() - 1;
+        let mut slot = self.entries[index].hash as usize & mask;
+        while self.table[slot] != index {
+            slot = (slot + 1) & mask;
+        }
+        slot
+    }
+
+    /// Record the position of a field just pushed, growing the table if
+    /// it would be more than 3/4 full
+    fn insert_slot(&mut self, index: usize) {
+        if self.entries.len() * 4 > self.table.len() * 3 {
+            self.table = vec![EMPTY; table_len(self.entries.len())];
+            self.rebuild_table();
+        } else {
+            self.place(index);
+        }
+    }
+
+    /// Put position `index` in the first free slot for its hash
+    fn place(&mut self, index: usize) {
+        let mask = self.table.len() - 1;
+        let mut slot = self.entries[index].hash as usize & mask;
+        while self.table[slot] != EMPTY {
+            slot = (slot + 1) & mask;
+        }
+        self.table[slot] = index;
+    }
+
+    /// Empty a slot, moving later entries of its probe run back so that
+    /// lookups still find them
+    fn clear_slot(&mut self, mut slot: usize) {
+        let mask = self.table.len() - 1;
+        self.table[slot] = EMPTY;
+        let mut next = slot;
+        loop {
+            next = (next + 1) & mask;
+            let index = self.table[next];
+            if index == EMPTY {
+                break;
+            }
+            let home = self.entries[index].hash as usize & mask;
+            if (next.wrapping_sub(home) & mask) >= (next.wrapping_sub(slot) & mask) {
+                self.table[slot] = index;
+                self.table[next] = EMPTY;
+                slot = next;
+            }
+        }
+    }
+
+    /// Rebuild the table from the stored hashes after the fields moved
+    fn rebuild_table(&mut self) {
+        self.table.fill(EMPTY);
+        for i in 0..self.entries.len() {
+            self.place(i);
+        }
+    }
+
+    /// Remove the field at position `index`, keeping the order of the rest
+    fn remove_index(&mut self, index: usize) -> Bucket<K, V> {
+        let slot = self.slot_of(index);
+        self.clear_slot(slot);
+        // Fields after the removed one move down by one position
+        for i in index + 1..self.entries.len() {
+            let slot = self.slot_of(i);
+            self.table[slot] = i - 1;
+        }
+        self.entries.remove(index)
+    }
+}
+
+impl<K: Hash + Eq, V> Map<K, V> {
+    /// Position of the field stored under `key`
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.table.is_empty() {
+            return None;
+        }
+        let hash = self.hasher.hash_one(key);
+        let mask = self.table.len() - 1;
+        let mut slot = hash as usize & mask;
+        loop {
+            let index = self.table[slot];
+            if index == EMPTY {
+                return None;
+            }
+            let bucket = &self.entries[index];
+            if bucket.hash == hash && bucket.key.borrow() == key {
+                return Some(index);
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    /// Get a reference to the value stored under `key`
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|i| &self.entries[i].value)
+    }
+
+    /// Get a mutable reference to the value stored under `key`
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|i| &mut self.entries[i].value)
+    }
+
+    /// Get the position of `key` in insertion order
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key)
+    }
+
+    /// Check if the map contains `key`
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Insert a field, returning the previous value if the key existed
+    ///
+    /// A new key is appended at the end; an existing key keeps its position
+    /// and metadata.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find(&key) {
+            Some(i) => Some(std::mem::replace(&mut self.entries[i].value, value)),
+            None => {
+                self.push_bucket(key, value, FieldMeta::default());
+                None
+            }
+        }
+    }
+
+    /// Insert a field together with its metadata, returning the previous value
+    pub fn insert_with_meta(&mut self, key: K, value: V, meta: FieldMeta) -> Option<V> {
+        match self.find(&key) {
+            Some(i) => {
+                let bucket = &mut self.entries[i];
+                bucket.meta = meta;
+                Some(std::mem::replace(&mut bucket.value, value))
+            }
+            None => {
+                self.push_bucket(key, value, meta);
+                None
+            }
+        }
+    }
+
+    /// Get the metadata of the field stored under `key`
+    pub fn meta<Q>(&self, key: &Q) -> Option<&FieldMeta>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|i| &self.entries[i].meta)
+    }
+
+    /// Get the metadata of the field stored under `key` mutably
+    pub fn meta_mut<Q>(&mut self, key: &Q) -> Option<&mut FieldMeta>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|i| &mut self.entries[i].meta)
+    }
+
+    /// Get the declaration of the field stored under `key`
+    pub fn declaration<Q>(&self, key: &Q) -> Option<&Declaration>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.meta(key).and_then(|m| m.declared.as_ref())
+    }
+
+    /// Get the doc comment of the field stored under `key`
+    pub fn doc<Q>(&self, key: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.meta(key).and_then(|m| m.doc.as_deref())
+    }
+
+    /// Append a field whose key is not in the map yet
+    fn push_bucket(&mut self, key: K, value: V, meta: FieldMeta) -> usize {
+        let hash = self.hasher.hash_one(&key);
+        let i = self.entries.len();
+        self.entries.push(Bucket {
+            hash,
+            key,
+            value,
+            meta,
+        });
+        self.insert_slot(i);
+        i
+    }
+
+    /// Remove a field, returning its value
+    ///
+    /// The remaining fields keep their relative order, which takes time
+    /// proportional to the number of fields after the removed one.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Remove a field, returning its key and value (see [`Map::remove`])
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.find(key)?;
+        let bucket = self.remove_index(i);
+        Some((bucket.key, bucket.value))
+    }
+
+    /// Get the entry for `key` for in-place manipulation
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.find(&key) {
+            Some(i) => Entry::Occupied(OccupiedEntry {
+                map: self,
+                index: i,
+            }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    /// Keep only the fields for which `keep` returns true
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let len = self.entries.len();
+        self.entries.retain_mut(|b| keep(&b.key, &mut b.value));
+        if self.entries.len() != len {
+            self.rebuild_table();
+        }
+    }
+
+    /// Sort fields by key
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.entries.sort_by(|a, b| a.key.cmp(&b.key));
+        self.rebuild_table();
+    }
+}
+
+impl<K, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Map<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq> PartialEq for Map<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(k, v)| other.get(k).is_some_and(|ov| v == ov))
+    }
+}
+
+impl<K, V, Q> std::ops::Index<&Q> for Map<K, V>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.get(key).expect("key not found in map")
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for Map<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V, FieldMeta)> for Map<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V, FieldMeta)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        for (k, v, meta) in iter {
+            map.insert_with_meta(k, v, meta);
+        }
+        map
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for Map<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> From<HashMap<K, V>> for Map<K, V> {
+    fn from(map: HashMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize> From<[(K, V); N]> for Map<K, V> {
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+/// A view into a single field of a map, which may be vacant or occupied
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// A field that is present in the map
+pub struct OccupiedEntry<'a, K, V> {
+    map: &'a mut Map<K, V>,
+    index: usize,
+}
+
+/// A field that is absent from the map
+pub struct VacantEntry<'a, K, V> {
+    map: &'a mut Map<K, V>,
+    key: K,
+}
+
+impl<'a, K: Hash + Eq, V> Entry<'a, K, V> {
+    /// Key of this entry
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Insert `default` if vacant and return the value
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default),
+        }
+    }
+
+    /// Insert the result of `default` if vacant and return the value
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Insert `V::default()` if vacant and return the value
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Modify the value in place if occupied
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(e) = &mut self {
+            f(e.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Hash + Eq, V> OccupiedEntry<'a, K, V> {
+    /// Key of this entry
+    pub fn key(&self) -> &K {
+        &self.map.entries[self.index].key
+    }
+
+    /// Reference to the value
+    pub fn get(&self) -> &V {
+        &self.map.entries[self.index].value
+    }
+
+    /// Mutable reference to the value
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.entries[self.index].value
+    }
+
+    /// Convert into a mutable reference bound to the map
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.entries[self.index].value
+    }
+
+    /// Replace the value, returning the old one
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the field, returning its value (see [`Map::remove`])
+    pub fn remove(self) -> V {
+        self.map.remove_index(self.index).value
+    }
+}
+
+impl<'a, K: Hash + Eq, V> VacantEntry<'a, K, V> {
+    /// Key of this entry
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Insert the value at the end of the map and return it
+    pub fn insert(self, value: V) -> &'a mut V {
+        let i = self.map.push_bucket(self.key, value, FieldMeta::default());
+        &mut self.map.entries[i].value
+    }
+}
+
+/// Borrowing iterator over map fields
+pub struct Iter<'a, K, V> {
+    inner: std::slice::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|b| (&b.key, &b.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|b| (&b.key, &b.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// Mutable iterator over map fields
+pub struct IterMut<'a, K, V> {
+    inner: std::slice::IterMut<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|b| (&b.key, &mut b.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|b| (&b.key, &mut b.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+/// Owning iterator over map fields
+pub struct IntoIter<K, V> {
+    inner: std::vec::IntoIter<Bucket<K, V>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|b| (b.key, b.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|b| (b.key, b.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<'a, K, V> IntoIterator for &'a Map<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Map<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> IntoIterator for Map<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.entries.into_iter(),
+        }
+    }
+}
@@ -1,26 +1,108 @@
 // Copyright (c) 2025 Vivian Burkhard Voss
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::borrowed::Value;
+use crate::config::{Dialect, ParseOptions};
 use crate::error::{Error, ErrorKind};
+use crate::escape::{unescape, unquote};
 use crate::lexer::{Lexer, Token};
-use crate::types::TypeHint;
-use crate::value::Value;
-use std::collections::HashMap;
+use crate::map::{FieldMeta, Map};
+use crate::types::{Declaration, LenRange, TypeHint};
+use std::borrow::{Borrow, Cow};
+use std::hash::Hash;
+use std::io::BufRead;
+
+/// Value tree built by the parser
+///
+/// [`Parser::parse`] builds borrowed values; [`parse`] and [`parse_reader`]
+/// build owned ones directly instead of converting a borrowed tree.
+trait Node<'a>: Sized {
+    type Key: Hash + Eq + Clone + Borrow<str>;
+
+    fn key(key: Cow<'a, str>) -> Self::Key;
+
+    /// Store a scalar (never an object or array)
+    fn scalar(value: Value<'a>) -> Self;
+
+    fn array(items: Vec<Self>) -> Self;
+
+    fn object(fields: Map<Self::Key, Self>) -> Self;
+}
+
+impl<'a> Node<'a> for Value<'a> {
+    type Key = Cow<'a, str>;
+
+    fn key(key: Cow<'a, str>) -> Self::Key {
+        key
+    }
+
+    fn scalar(value: Value<'a>) -> Self {
+        value
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        Value::Array(items)
+    }
+
+    fn object(fields: Map<Self::Key, Self>) -> Self {
+        Value::Object(fields)
+    }
+}
+
+impl<'a> Node<'a> for crate::Value {
+    type Key = String;
+
+    fn key(key: Cow<'a, str>) -> Self::Key {
+        key.into_owned()
+    }
+
+    fn scalar(value: Value<'a>) -> Self {
+        value.into_owned()
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        crate::Value::Array(items)
+    }
+
+    fn object(fields: Map<Self::Key, Self>) -> Self {
+        crate::Value::Object(fields)
+    }
+}
 
 /// Parser for GBLN format using recursive descent
+///
+/// Produces a [`borrowed::Value`](crate::borrowed::Value) whose keys and
+/// strings are slices of the input.
 pub struct Parser<'a> {
     lexer: Lexer<'a>,
-    current_token: Token,
+    current_token: Token<'a>,
+    options: ParseOptions,
 }
 
 impl<'a> Parser<'a> {
     /// Create a new parser from input string
     pub fn new(input: &'a str) -> Result<Self, Error> {
-        let mut lexer = Lexer::new(input);
+        Parser::with_options(input, ParseOptions::default())
+    }
+
+    /// Create a new parser with the given options
+    pub fn with_options(input: &'a str, options: ParseOptions) -> Result<Self, Error> {
+        Parser::from_lexer(Lexer::new(input), options)
+    }
+
+    /// Create a parser that streams its input from a reader
+    ///
+    /// Keys and strings in the result own their text.
+    pub fn from_reader<R: BufRead + 'a>(reader: R, options: ParseOptions) -> Result<Self, Error> {
+        Parser::from_lexer(Lexer::from_reader(reader), options)
+    }
+
+    fn from_lexer(mut lexer: Lexer<'a>, options: ParseOptions) -> Result<Self, Error> {
         let current_token = lexer.next_token()?;
         Ok(Parser {
             lexer,
             current_token,
+            options,
         })
     }
 
@@ -31,7 +113,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Expect a specific token and advance
-    fn expect(&mut self, expected: Token) -> Result<(), Error> {
+    fn expect(&mut self, expected: Token<'a>) -> Result<(), Error> {
         if std::mem::discriminant(&self.current_token) != std::mem::discriminant(&expected) {
             return Err(Error::new(
                 ErrorKind::UnexpectedToken,
@@ -44,7 +126,12 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse the entire document
-    pub fn parse(&mut self) -> Result<Value, Error> {
+    pub fn parse(&mut self) -> Result<Value<'a>, Error> {
+        self.parse_document()
+    }
+
+    /// Parse the entire document into the given kind of value tree
+    fn parse_document<V: Node<'a>>(&mut self) -> Result<V, Error> {
         let value = self.parse_value()?;
 
         // Ensure we consumed entire input
@@ -61,9 +148,9 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse any value (object, array, or typed single value)
-    fn parse_value(&mut self) -> Result<Value, Error> {
+    fn parse_value<V: Node<'a>>(&mut self) -> Result<V, Error> {
         match &self.current_token {
-            Token::Key(_) => {
+            Token::Key(_) | Token::QuotedKey(_) => {
                 // Could be object field or typed array start
                 self.parse_keyed_value()
             }
@@ -77,7 +164,7 @@ impl<'a> Parser<'a> {
             }
             Token::LBracket => {
                 // Anonymous array
-                self.parse_array()
+                self.parse_array(None)
             }
             _ => Err(Error::new(
                 ErrorKind::UnexpectedToken,
@@ -88,19 +175,25 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Take the current token as an object key, resolving quotes and escapes
+    fn current_key(&self, expected: &str) -> Result<Cow<'a, str>, Error> {
+        match &self.current_token {
+            Token::Key(k) => Ok(k.clone()),
+            Token::QuotedKey(raw) => key_name(raw)
+                .map_err(|e| e.at(self.lexer.current_line(), self.lexer.current_column())),
+            _ => Err(Error::new(
+                ErrorKind::UnexpectedToken,
+                self.lexer.current_line(),
+                self.lexer.current_column(),
+                expected.to_string(),
+            )),
+        }
+    }
+
     /// Parse keyed value (object, typed/untyped single value, or typed array)
-    fn parse_keyed_value(&mut self) -> Result<Value, Error> {
-        let key = match &self.current_token {
-            Token::Key(k) => k.clone(),
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::UnexpectedToken,
-                    self.lexer.current_line(),
-                    self.lexer.current_column(),
-                    "Expected key".to_string(),
-                ))
-            }
-        };
+    fn parse_keyed_value<V: Node<'a>>(&mut self) -> Result<V, Error> {
+        let doc = self.lexer.take_doc();
+        let key = self.current_key("Expected key")?;
 
         self.advance()?;
 
@@ -109,54 +202,30 @@ impl<'a> Parser<'a> {
                 // Untyped single value: key(value)
                 let value = self.parse_untyped_single_value()?;
                 // Wrap in object with single field
-                let mut obj = HashMap::new();
-                obj.insert(key, value);
-                Ok(Value::Object(obj))
+                Ok(single_field(key, value, Some(Declaration::Untyped), doc))
             }
             Token::LBrace => {
                 // Object: key{...}
                 let inner_obj = self.parse_object()?;
                 // Wrap with the key
-                let mut obj = HashMap::new();
-                obj.insert(key, inner_obj);
-                Ok(Value::Object(obj))
+                Ok(single_field(key, inner_obj, None, doc))
             }
             Token::LBracket => {
                 // Untyped array: key[...]
                 let values = self.parse_untyped_array_content()?;
                 // Wrap in object with single field
-                let mut obj = HashMap::new();
-                obj.insert(key, Value::Array(values));
-                Ok(Value::Object(obj))
+                Ok(single_field(
+                    key,
+                    V::array(values),
+                    Some(Declaration::Untyped),
+                    doc,
+                ))
             }
             Token::LAngle => {
-                // Could be typed single value or typed array
-                let type_hint = self.parse_type_hint()?;
-
-                match &self.current_token {
-                    Token::LParen => {
-                        // Typed single value: key<type>(value)
-                        let value = self.parse_single_value_content(&type_hint)?;
-                        // Wrap in object with single field
-                        let mut obj = HashMap::new();
-                        obj.insert(key, value);
-                        Ok(Value::Object(obj))
-                    }
-                    Token::LBracket => {
-                        // Typed array: key<type>[...]
-                        let values = self.parse_typed_array_content(&type_hint)?;
-                        // Wrap in object with single field
-                        let mut obj = HashMap::new();
-                        obj.insert(key, Value::Array(values));
-                        Ok(Value::Object(obj))
-                    }
-                    _ => Err(Error::new(
-                        ErrorKind::UnexpectedToken,
-                        self.lexer.current_line(),
-                        self.lexer.current_column(),
-                        "Expected '(' or '[' after type hint".to_string(),
-                    )),
-                }
+                // Typed single value, typed array or table
+                let (value, declared) = self.parse_hinted_field()?;
+                // Wrap in object with single field
+                Ok(single_field(key, value, Some(declared), doc))
             }
             _ => Err(Error::new(
                 ErrorKind::InvalidSyntax,
@@ -171,16 +240,17 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse object: {...}
-    fn parse_object(&mut self) -> Result<Value, Error> {
+    fn parse_object<V: Node<'a>>(&mut self) -> Result<V, Error> {
         self.expect(Token::LBrace)?;
 
-        let mut fields = HashMap::new();
+        let mut fields = Map::new();
 
         while !matches!(self.current_token, Token::RBrace | Token::Eof) {
-            let (key, value) = self.parse_object_field()?;
+            let doc = self.lexer.take_doc();
+            let (key, value, declared) = self.parse_object_field()?;
 
             // Check for duplicate keys
-            if fields.contains_key(&key) {
+            if fields.contains_key(key.as_ref()) {
                 return Err(Error::new(
                     ErrorKind::DuplicateKey,
                     self.lexer.current_line(),
@@ -189,26 +259,18 @@ impl<'a> Parser<'a> {
                 ));
             }
 
-            fields.insert(key, value);
+            fields.insert_with_meta(V::key(key), value, FieldMeta { declared, doc });
         }
 
         self.expect(Token::RBrace)?;
-        Ok(Value::Object(fields))
+        Ok(V::object(fields))
     }
 
     /// Parse single object field: key(value), key<type>(value), key{...}, key[...], or key<type>[...]
-    fn parse_object_field(&mut self) -> Result<(String, Value), Error> {
-        let key = match &self.current_token {
-            Token::Key(k) => k.clone(),
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::UnexpectedToken,
-                    self.lexer.current_line(),
-                    self.lexer.current_column(),
-                    "Expected key in object field".to_string(),
-                ))
-            }
-        };
+    fn parse_object_field<V: Node<'a>>(
+        &mut self,
+    ) -> Result<(Cow<'a, str>, V, Option<Declaration>), Error> {
+        let key = self.current_key("Expected key in object field")?;
 
         self.advance()?;
 
@@ -217,43 +279,23 @@ impl<'a> Parser<'a> {
             Token::LParen => {
                 // Untyped single value: key(value)
                 let value = self.parse_untyped_single_value()?;
-                Ok((key, value))
+                Ok((key, value, Some(Declaration::Untyped)))
             }
             Token::LBrace => {
                 // Nested object: key{...}
                 let value = self.parse_object()?;
-                Ok((key, value))
+                Ok((key, value, None))
             }
             Token::LBracket => {
                 // Array: key[...]
-                let value = self.parse_array()?;
-                Ok((key, value))
+                let value = self.parse_array(None)?;
+                Ok((key, value, Some(Declaration::Untyped)))
             }
             Token::LAngle => {
-                // Typed value or typed array: key<type>(value) or key<type>[...]
-                let type_hint = self.parse_type_hint()?;
-
-                match &self.current_token {
-                    Token::LParen => {
-                        // Typed single value: key<type>(value)
-                        let value = self.parse_single_value_content(&type_hint)?;
-                        Ok((key, value))
-                    }
-                    Token::LBracket => {
-                        // Typed array: key<type>[...]
-                        let values = self.parse_typed_array_content(&type_hint)?;
-                        Ok((key, Value::Array(values)))
-                    }
-                    _ => Err(Error::new(
-                        ErrorKind::UnexpectedToken,
-                        self.lexer.current_line(),
-                        self.lexer.current_column(),
-                        format!(
-                            "Expected '(' or '[' after type hint, found {:?}",
-                            self.current_token
-                        ),
-                    )),
-                }
+                // Typed value, typed array or table: key<type>(value),
+                // key<type>[...] or key<{col<type> ...}>[(...) ...]
+                let (value, declared) = self.parse_hinted_field()?;
+                Ok((key, value, Some(declared)))
             }
             _ => Err(Error::new(
                 ErrorKind::UnexpectedToken,
@@ -267,37 +309,115 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Parse type hint: <type>
-    fn parse_type_hint(&mut self) -> Result<TypeHint, Error> {
-        self.expect(Token::LAngle)?;
-
-        let type_str = match &self.current_token {
-            Token::Key(t) | Token::Type(t) => t.clone(),
+    /// Parse the rest of a field whose key is followed by a hint:
+    /// <type>(value), <type>[...], <;len>[...] or <{col<type> ...}>[(...) ...]
+    fn parse_hinted_field<V: Node<'a>>(&mut self) -> Result<(V, Declaration), Error> {
+        let line = self.lexer.current_line();
+        let column = self.lexer.current_column();
+        let (hint, len) = self.parse_hint()?;
+
+        let (value, declared) = match (hint, &self.current_token) {
+            (Hint::Table(columns), _) => {
+                // Table: key<{col<type> ...}>[(...) ...]
+                let rows = self.parse_table_content(&columns, len)?;
+                (V::array(rows), table_declaration(&columns))
+            }
+            (Hint::Type(type_hint), Token::LParen) if len.is_none() => {
+                // Typed single value: key<type>(value)
+                let value = self.parse_single_value_content(&type_hint)?;
+                (value, Declaration::Typed(type_hint))
+            }
+            (Hint::Type(type_hint), Token::LBracket) => {
+                // Typed array: key<type>[...]
+                let values = self.parse_typed_array_content(&type_hint, len)?;
+                (V::array(values), Declaration::TypedArray(type_hint))
+            }
+            (Hint::Any, Token::LBracket) => {
+                // Untyped array with length bounds: key<;len>[...]
+                (self.parse_array(len)?, Declaration::Untyped)
+            }
+            (_, Token::LParen) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidTypeHint,
+                    line,
+                    column,
+                    "Array length bounds are only allowed on arrays".to_string(),
+                ))
+            }
             _ => {
                 return Err(Error::new(
                     ErrorKind::UnexpectedToken,
                     self.lexer.current_line(),
                     self.lexer.current_column(),
-                    "Expected type hint".to_string(),
+                    format!(
+                        "Expected '(' or '[' after type hint, found {:?}",
+                        self.current_token
+                    ),
                 ))
             }
         };
 
-        self.advance()?;
-        self.expect(Token::RAngle)?;
+        let declared = match len {
+            Some(len) => Declaration::Bounded(Box::new(declared), len),
+            None => declared,
+        };
+        Ok((value, declared))
+    }
 
-        TypeHint::from_str(&type_str).map_err(|e| {
-            Error::new(
-                ErrorKind::InvalidTypeHint,
+    /// Parse type hint or table schema, with optional array length bounds:
+    /// <type>, <{col<type> ...}>, <type;len> or <;len>
+    fn parse_hint(&mut self) -> Result<(Hint<'a>, Option<LenRange>), Error> {
+        // Check for LAngle but DON'T call advance() - hints are read raw
+        if !matches!(self.current_token, Token::LAngle) {
+            return Err(Error::new(
+                ErrorKind::UnexpectedToken,
                 self.lexer.current_line(),
                 self.lexer.current_column(),
-                e,
-            )
-        })
+                format!("Expected '<', found {:?}", self.current_token),
+            ));
+        }
+
+        // Read raw hint directly from lexer (consumes the closing >)
+        let line = self.lexer.current_line();
+        let column = self.lexer.current_column();
+        let content = self.lexer.read_type_hint_content()?;
+
+        // Refresh current_token after raw read
+        self.current_token = self.lexer.next_token()?;
+
+        match content {
+            Cow::Borrowed(content) => parse_hint_text(content, line, column),
+            Cow::Owned(content) => {
+                parse_hint_text(&content, line, column).map(|(hint, len)| (hint.into_owned(), len))
+            }
+        }
+    }
+
+    /// Fail before reading an array element past the maximum length
+    fn check_push(&self, len: Option<LenRange>, count: usize) -> Result<(), Error> {
+        match len {
+            Some(len) => len
+                .check_push(count)
+                .map_err(|e| e.at(self.lexer.current_line(), self.lexer.current_column())),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail if a complete array is outside its length bounds
+    fn check_len(&self, len: Option<LenRange>, count: usize) -> Result<(), Error> {
+        match len {
+            Some(len) => len
+                .check_len(count)
+                .map_err(|e| e.at(self.lexer.current_line(), self.lexer.current_column())),
+            None => Ok(()),
+        }
     }
 
     /// Parse single value content: (value)
-    fn parse_single_value_content(&mut self, type_hint: &TypeHint) -> Result<Value, Error> {
+    fn parse_single_value_content<V: Node<'a>>(
+        &mut self,
+        type_hint: &TypeHint,
+    ) -> Result<V, Error> {
         // Check for LParen but DON'T call advance() - we need to read raw content
         if !matches!(self.current_token, Token::LParen) {
             return Err(Error::new(
@@ -311,24 +431,16 @@ impl<'a> Parser<'a> {
         // Read raw content directly from lexer (bypassing tokenization)
         // The lexer is positioned right after the ( token
         // This also consumes the closing )
-        let content = self.lexer.read_parenthesized_content()?;
-
-        // Refresh current_token after raw read
-        self.current_token = self.lexer.next_token()?;
+        let content = self.read_value_content()?;
 
         // Parse content according to type hint
-        type_hint.parse_value(&content).map_err(|e| {
-            Error::new(
-                ErrorKind::TypeMismatch,
-                self.lexer.current_line(),
-                self.lexer.current_column(),
-                e,
-            )
-        })
+        Value::parse_typed_with_options(type_hint, content, &self.options)
+            .map(V::scalar)
+            .map_err(|e| e.at(self.lexer.current_line(), self.lexer.current_column()))
     }
 
     /// Parse untyped single value: (value) with type inference
-    fn parse_untyped_single_value(&mut self) -> Result<Value, Error> {
+    fn parse_untyped_single_value<V: Node<'a>>(&mut self) -> Result<V, Error> {
         // Check for LParen but DON'T call advance() - we need to read raw content
         if !matches!(self.current_token, Token::LParen) {
             return Err(Error::new(
@@ -340,63 +452,74 @@ impl<'a> Parser<'a> {
         }
 
         // Read raw content directly from lexer
+        let content = self.read_value_content()?;
+
+        // Infer type from content
+        self.infer_value(content)
+    }
+
+    /// Read the content of (value) with escape sequences resolved
+    ///
+    /// The lexer is positioned right after the ( token; the closing ) is
+    /// consumed as well.
+    fn read_value_content(&mut self) -> Result<Cow<'a, str>, Error> {
+        let line = self.lexer.current_line();
+        let column = self.lexer.current_column();
         let content = self.lexer.read_parenthesized_content()?;
 
         // Refresh current_token after raw read
         self.current_token = self.lexer.next_token()?;
 
-        // Infer type from content
-        self.infer_value(&content)
+        let content = match content {
+            Cow::Borrowed(content) => unescape(content),
+            Cow::Owned(content) if !content.contains('\\') => Ok(Cow::Owned(content)),
+            Cow::Owned(content) => unescape(&content).map(|s| Cow::Owned(s.into_owned())),
+        };
+        content.map_err(|e| e.at(line, column))
     }
 
     /// Infer and parse value from string content
-    fn infer_value(&self, content: &str) -> Result<Value, Error> {
-        // Try parsing in order of specificity
-
-        // 1. Check for null/empty
-        if content.is_empty() || content == "null" {
-            return Ok(Value::Null);
-        }
-
-        // 2. Check for boolean
-        if content == "t" || content == "true" {
-            return Ok(Value::Bool(true));
-        }
-        if content == "f" || content == "false" {
-            return Ok(Value::Bool(false));
-        }
-
-        // 3. Try as integer
-        if let Ok(n) = content.parse::<i64>() {
-            return Ok(Value::I64(n));
-        }
-
-        // 4. Try as float
-        if let Ok(n) = content.parse::<f64>() {
-            return Ok(Value::F64(n));
+    fn infer_value<V: Node<'a>>(&self, content: Cow<'a, str>) -> Result<V, Error> {
+        let value = infer_scalar(content, self.options.dialect, self.options.infer_aliases);
+        if let Value::F64(n) = value {
+            if !n.is_finite() && !self.options.allow_non_finite {
+                return Err(Error::new(
+                    ErrorKind::NonFiniteFloat,
+                    self.lexer.current_line(),
+                    self.lexer.current_column(),
+                    format!(
+                        "Non-finite float '{}' is not allowed",
+                        if n.is_nan() {
+                            "nan".to_string()
+                        } else {
+                            n.to_string()
+                        }
+                    ),
+                ));
+            }
         }
-
-        // 5. Default to string (no length limit without type hint)
-        Ok(Value::Str(content.to_string()))
+        Ok(V::scalar(value))
     }
 
     /// Parse array: [...]
-    fn parse_array(&mut self) -> Result<Value, Error> {
+    fn parse_array<V: Node<'a>>(&mut self, len: Option<LenRange>) -> Result<V, Error> {
         self.expect(Token::LBracket)?;
 
         let mut items = Vec::new();
 
         while !matches!(self.current_token, Token::RBracket | Token::Eof) {
+            self.check_push(len, items.len())?;
             let value = self.parse_array_item()?;
             items.push(value);
         }
 
+        self.check_len(len, items.len())?;
         self.expect(Token::RBracket)?;
-        Ok(Value::Array(items))
+        Ok(V::array(items))
     }
 
     /// Parse single array item (typed value, untyped value, or object)
-    fn parse_array_item(&mut self) -> Result<Value, Error> {
+    fn parse_array_item<V: Node<'a>>(&mut self) -> Result<V, Error> {
         match &self.current_token {
             Token::LAngle => {
                 // Typed value in array: <type>(value)
@@ -414,7 +537,7 @@ impl<'a> Parser<'a> {
                     _ => unreachable!(),
                 };
                 self.advance()?;
-                self.infer_value(&content)
+                self.infer_value(content)
             }
             _ => Err(Error::new(
                 ErrorKind::UnexpectedToken,
@@ -425,15 +548,35 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Parse typed single value: <type>(value)
-    fn parse_typed_single_value(&mut self) -> Result<Value, Error> {
-        let type_hint = self.parse_type_hint()?;
-        self.parse_single_value_content(&type_hint)
+    /// Parse typed value without a key: <type>(value), <type>[...],
+    /// <type;len>[...] or <;len>[...]
+    fn parse_typed_single_value<V: Node<'a>>(&mut self) -> Result<V, Error> {
+        let line = self.lexer.current_line();
+        let column = self.lexer.current_column();
+
+        let message = match (self.parse_hint()?, &self.current_token) {
+            ((Hint::Type(type_hint), len), Token::LBracket) => {
+                let values = self.parse_typed_array_content(&type_hint, len)?;
+                return Ok(V::array(values));
+            }
+            ((Hint::Any, len), Token::LBracket) => return self.parse_array(len),
+            ((Hint::Type(type_hint), None), _) => {
+                return self.parse_single_value_content(&type_hint)
+            }
+            ((Hint::Table(_), _), _) => "Table schemas are only allowed on keyed arrays",
+            _ => "Array length bounds are only allowed on arrays",
+        };
+        Err(Error::new(
+            ErrorKind::InvalidTypeHint,
+            line,
+            column,
+            message.to_string(),
+        ))
     }
 
     /// Parse typed array content: [val1 val2 val3]
     /// Parse untyped array content: [value1 value2 value3] with type inference
-    fn parse_untyped_array_content(&mut self) -> Result<Vec<Value>, Error> {
+    fn parse_untyped_array_content<V: Node<'a>>(&mut self) -> Result<Vec<V>, Error> {
         self.expect(Token::LBracket)?;
 
         let mut items = Vec::new();
@@ -453,7 +596,7 @@ impl<'a> Parser<'a> {
                     // Untyped value - infer type from content
                     let value_str = s.clone();
                     self.advance()?;
-                    self.infer_value(&value_str)?
+                    self.infer_value(value_str)?
                 }
                 _ => {
                     return Err(Error::new(
@@ -472,12 +615,18 @@ impl<'a> Parser<'a> {
         Ok(items)
     }
 
-    fn parse_typed_array_content(&mut self, type_hint: &TypeHint) -> Result<Vec<Value>, Error> {
+    fn parse_typed_array_content<V: Node<'a>>(
+        &mut self,
+        type_hint: &TypeHint,
+        len: Option<LenRange>,
+    ) -> Result<Vec<V>, Error> {
         self.expect(Token::LBracket)?;
 
         let mut items = Vec::new();
 
         while !matches!(self.current_token, Token::RBracket | Token::Eof) {
+            self.check_push(len, items.len())?;
+
             // Read raw value token
             let value_str = match &self.current_token {
                 Token::Key(s) | Token::Type(s) => s.clone(),
@@ -491,26 +640,372 @@ impl<'a> Parser<'a> {
                 }
             };
 
-            let value = type_hint.parse_value(&value_str).map_err(|e| {
-                Error::new(
-                    ErrorKind::TypeMismatch,
-                    self.lexer.current_line(),
-                    self.lexer.current_column(),
-                    e,
-                )
-            })?;
+            let value = Value::parse_typed_with_options(type_hint, value_str, &self.options)
+                .map(V::scalar)
+                .map_err(|e| e.at(self.lexer.current_line(), self.lexer.current_column()))?;
 
             items.push(value);
             self.advance()?;
         }
 
+        self.check_len(len, items.len())?;
         self.expect(Token::RBracket)?;
         Ok(items)
     }
+
+    /// Parse table rows: [(val1 val2) (val1 val2)]
+    ///
+    /// Each row becomes an object with one field per column, validated
+    /// against the column's type hint.
+    fn parse_table_content<V: Node<'a>>(
+        &mut self,
+        columns: &[(Cow<'a, str>, TypeHint)],
+        len: Option<LenRange>,
+    ) -> Result<Vec<V>, Error> {
+        if !matches!(self.current_token, Token::LBracket) {
+            return Err(Error::new(
+                ErrorKind::UnexpectedToken,
+                self.lexer.current_line(),
+                self.lexer.current_column(),
+                format!(
+                    "Expected '[' after table schema, found {:?}",
+                    self.current_token
+                ),
+            ));
+        }
+        self.advance()?;
+
+        let keys: Vec<V::Key> = columns
+            .iter()
+            .map(|(name, _)| V::key(name.clone()))
+            .collect();
+        let mut rows = Vec::new();
+
+        while !matches!(self.current_token, Token::RBracket | Token::Eof) {
+            self.check_push(len, rows.len())?;
+            self.expect(Token::LParen)?;
+
+            let mut row = Map::with_capacity(columns.len());
+            let mut count = 0;
+
+            while !matches!(self.current_token, Token::RParen | Token::Eof) {
+                let value_str = match &self.current_token {
+                    Token::Key(s) | Token::Type(s) => s.clone(),
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::UnexpectedToken,
+                            self.lexer.current_line(),
+                            self.lexer.current_column(),
+                            "Expected value in table row".to_string(),
+                        ))
+                    }
+                };
+
+                count += 1;
+                if let (Some(key), Some((_, hint))) = (keys.get(count - 1), columns.get(count - 1))
+                {
+                    let value = Value::parse_typed_with_options(hint, value_str, &self.options)
+                        .map(V::scalar)
+                        .map_err(|e| {
+                            e.at(self.lexer.current_line(), self.lexer.current_column())
+                        })?;
+                    row.insert_with_meta(
+                        key.clone(),
+                        value,
+                        FieldMeta {
+                            declared: Some(Declaration::Typed(hint.clone())),
+                            doc: None,
+                        },
+                    );
+                }
+                self.advance()?;
+            }
+
+            if count != columns.len() {
+                let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_ref()).collect();
+                return Err(Error::new(
+                    ErrorKind::ColumnCountMismatch,
+                    self.lexer.current_line(),
+                    self.lexer.current_column(),
+                    format!(
+                        "Table row {} has {} values, expected {} ({})",
+                        rows.len() + 1,
+                        count,
+                        columns.len(),
+                        names.join(" ")
+                    ),
+                ));
+            }
+
+            self.expect(Token::RParen)?;
+            rows.push(V::object(row));
+        }
+
+        self.check_len(len, rows.len())?;
+        self.expect(Token::RBracket)?;
+        Ok(rows)
+    }
+}
+
+/// Contents of `<...>` before any length bounds
+enum Hint<'a> {
+    /// A type hint
+    Type(TypeHint),
+
+    /// A table schema
+    Table(Vec<(Cow<'a, str>, TypeHint)>),
+
+    /// Nothing: an untyped array with length bounds only (`<;..16>`)
+    Any,
+}
+
+impl Hint<'_> {
+    /// Detach column names from the hint text
+    fn into_owned(self) -> Hint<'static> {
+        match self {
+            Hint::Type(hint) => Hint::Type(hint),
+            Hint::Table(columns) => Hint::Table(
+                columns
+                    .into_iter()
+                    .map(|(name, hint)| (Cow::Owned(name.into_owned()), hint))
+                    .collect(),
+            ),
+            Hint::Any => Hint::Any,
+        }
+    }
+}
+
+/// Parse the text of a type hint or table schema, with optional array
+/// length bounds
+fn parse_hint_text(
+    content: &str,
+    line: usize,
+    column: usize,
+) -> Result<(Hint<'_>, Option<LenRange>), Error> {
+    let content = content.trim();
+
+    // Array length bounds after the last `;` (`u8;..16`, `f32;3`)
+    let (content, len) = match content.rsplit_once(';') {
+        Some((hint, len)) if is_len_text(len.trim()) => {
+            let len = LenRange::parse(len.trim())
+                .map_err(|e| Error::new(ErrorKind::InvalidTypeHint, line, column, e))?;
+            (hint.trim(), Some(len))
+        }
+        _ => (content, None),
+    };
+
+    if content.is_empty() {
+        if len.is_some() {
+            return Ok((Hint::Any, len));
+        }
+        return Err(Error::new(
+            ErrorKind::UnexpectedToken,
+            line,
+            column,
+            "Expected type hint".to_string(),
+        ));
+    }
+
+    let hint = if content.starts_with('{') {
+        parse_table_schema(content).map(Hint::Table)
+    } else {
+        TypeHint::from_str(content).map(Hint::Type)
+    };
+    hint.map(|hint| (hint, len))
+        .map_err(|e| Error::new(ErrorKind::InvalidTypeHint, line, column, e))
+}
+
+/// Check if text after `;` in a hint is meant as array length bounds
+fn is_len_text(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+}
+
+/// Parse a table schema `{col<type> ...}` into column names and hints
+fn parse_table_schema(content: &str) -> Result<Vec<(Cow<'_, str>, TypeHint)>, String> {
+    let invalid = |reason: String| format!("Invalid table schema {}: {}", content, reason);
+
+    let mut lexer = Lexer::new(content);
+    let mut columns: Vec<(Cow<'_, str>, TypeHint)> = Vec::new();
+
+    // Opening brace, checked by the caller
+    lexer.next_token().map_err(|e| invalid(e.message))?;
+
+    loop {
+        let name = match lexer.next_token().map_err(|e| invalid(e.message))? {
+            Token::RBrace => break,
+            Token::Key(name) => name,
+            Token::QuotedKey(raw) => key_name(&raw).map_err(|e| invalid(e.message))?,
+            token => return Err(invalid(format!("unexpected {:?}", token))),
+        };
+
+        if lexer.next_token().map_err(|e| invalid(e.message))? != Token::LAngle {
+            return Err(invalid(format!("column '{}' has no type hint", name)));
+        }
+        let hint = lexer
+            .read_type_hint_content()
+            .map_err(|e| invalid(e.message))?;
+        let hint = TypeHint::from_str(hint.trim()).map_err(invalid)?;
+
+        if columns.iter().any(|(existing, _)| *existing == name) {
+            return Err(invalid(format!("duplicate column '{}'", name)));
+        }
+        columns.push((name, hint));
+    }
+
+    if lexer.next_token().map_err(|e| invalid(e.message))? != Token::Eof {
+        return Err(invalid("content after closing brace".to_string()));
+    }
+    if columns.is_empty() {
+        return Err(invalid("no columns".to_string()));
+    }
+
+    Ok(columns)
+}
+
+/// Key text of a quoted key token, borrowed unless it contains escapes
+fn key_name<'a>(raw: &Cow<'a, str>) -> Result<Cow<'a, str>, Error> {
+    match raw {
+        Cow::Borrowed(raw) => unquote(raw),
+        Cow::Owned(raw) => unquote(raw).map(|key| Cow::Owned(key.into_owned())),
+    }
+}
+
+/// Declaration recorded for a field written as a table
+fn table_declaration(columns: &[(Cow<'_, str>, TypeHint)]) -> Declaration {
+    Declaration::Table(
+        columns
+            .iter()
+            .map(|(name, hint)| (name.to_string(), hint.clone()))
+            .collect(),
+    )
+}
+
+/// Infer the type of untyped value content
+///
+/// Shared with the serialiser, which uses it to check whether a value can be
+/// written back without a type hint. Without `aliases`, the lenient `yes`,
+/// `no` and `n` spellings are left as strings.
+pub(crate) fn infer_scalar(content: Cow<'_, str>, dialect: Dialect, aliases: bool) -> Value<'_> {
+    // Try parsing in order of specificity
+    let alias = matches!(&*content, "yes" | "no" | "n");
+    if alias && !aliases {
+        return Value::Str(content);
+    }
+
+    // 1. Check for null/empty
+    if dialect.is_null(&content) {
+        return Value::Null;
+    }
+
+    // 2. Try as integer, widening to the first type that holds it (before
+    // booleans, so that `0` and `1` stay integers in the lenient dialect)
+    if dialect.allows_number(&content) {
+        if let Ok(n) = content.parse::<i64>() {
+            return Value::I64(n);
+        }
+        if let Ok(n) = content.parse::<u64>() {
+            return Value::U64(n);
+        }
+        if let Ok(n) = content.parse::<i128>() {
+            return Value::I128(n);
+        }
+        if let Ok(n) = content.parse::<u128>() {
+            return Value::U128(n);
+        }
+    }
+
+    // 3. Check for boolean
+    if let Some(b) = dialect.parse_bool(&content) {
+        return Value::Bool(b);
+    }
+
+    // 4. Try as float (an integer literal too large for u128 stays a string
+    // rather than losing digits)
+    let digits = content.strip_prefix(['-', '+']).unwrap_or(&content);
+    let is_integer_literal = !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit());
+    if !is_integer_literal && dialect.allows_number(&content) {
+        if let Ok(n) = content.parse::<f64>() {
+            return Value::F64(n);
+        }
+    }
+
+    // 5. Default to string (no length limit without type hint)
+    Value::Str(content)
+}
+
+/// Wrap a top-level `key...` value in an object with a single field
+fn single_field<'a, V: Node<'a>>(
+    key: Cow<'a, str>,
+    value: V,
+    declared: Option<Declaration>,
+    doc: Option<String>,
+) -> V {
+    let mut obj = Map::new();
+    obj.insert_with_meta(V::key(key), value, FieldMeta { declared, doc });
+    V::object(obj)
 }
 
 /// Public parse function
-pub fn parse(input: &str) -> Result<Value, Error> {
+pub fn parse(input: &str) -> Result<crate::Value, Error> {
+    parse_with_options(input, &ParseOptions::default())
+}
+
+/// Parse with the given options (see [`ParseOptions`])
+pub fn parse_with_options(input: &str, options: &ParseOptions) -> Result<crate::Value, Error> {
+    let mut parser = Parser::with_options(input, options.clone())?;
+    parser.parse_document()
+}
+
+/// Parse a document streamed from a reader
+///
+/// The input is decoded incrementally, so only the text of the token being
+/// read is held in memory next to the result. Read errors and invalid UTF-8
+/// are reported as [`ErrorKind::IoError`].
+///
+/// ```
+/// use gbln::parse_reader;
+///
+/// let input: &[u8] = b"user{name<s32>(Alice)}";
+/// let value = parse_reader(input).unwrap();
+/// assert_eq!(value["user"]["name"].as_str(), Some("Alice"));
+/// ```
+pub fn parse_reader<R: BufRead>(reader: R) -> Result<crate::Value, Error> {
+    parse_reader_with_options(reader, &ParseOptions::default())
+}
+
+/// Parse a document streamed from a reader, with the given options (see
+/// [`ParseOptions`])
+pub fn parse_reader_with_options<R: BufRead>(
+    reader: R,
+    options: &ParseOptions,
+) -> Result<crate::Value, Error> {
+    let mut parser = Parser::from_reader(reader, options.clone())?;
+    parser.parse_document()
+}
+
+/// Parse without copying keys and strings out of the input
+///
+/// The result borrows from `input`; use
+/// [`into_owned`](crate::borrowed::Value::into_owned) to detach it.
+///
+/// ```
+/// use gbln::parse_borrowed;
+/// use std::borrow::Cow;
+///
+/// let input = "user{name<s32>(Alice)}";
+/// let value = parse_borrowed(input).unwrap();
+/// assert!(matches!(value["user"]["name"], gbln::borrowed::Value::Str(Cow::Borrowed("Alice"))));
+/// ```
+pub fn parse_borrowed(input: &str) -> Result<Value<'_>, Error> {
     let mut parser = Parser::new(input)?;
     parser.parse()
 }
+
+/// Parse without copying, with the given options (see [`ParseOptions`])
+pub fn parse_borrowed_with_options<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> Result<Value<'a>, Error> {
+    let mut parser = Parser::with_options(input, options.clone())?;
+    parser.parse()
+}
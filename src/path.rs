@@ -0,0 +1,405 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! GBLN Value Paths
+//!
+//! Dotted path syntax for deep lookups, e.g. `app.server.port` or
+//! `users[3].name`. Keys are separated by `.`, array positions are written
+//! as `[N]`. Keys containing `.` or `[` are quoted as in GBLN source
+//! (`labels."v1.2"`). The empty path refers to the root value.
+
+use crate::error::{Error, ErrorKind};
+use crate::escape::{quote, unescape};
+use crate::map::Map;
+use crate::value::Value;
+use std::fmt;
+
+/// A single step in a path
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// Object field
+    Key(String),
+
+    /// Array position
+    Index(usize),
+}
+
+/// A location in a value tree
+///
+/// Displays in path syntax (`users[3].name`), so it can be fed back into
+/// [`Value::get_path`]. The empty path is the root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ValuePath {
+    segments: Vec<PathSegment>,
+}
+
+impl ValuePath {
+    /// The root path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Segments from the root down
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Check if this is the root path
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Last segment, i.e. the key or position of the value in its parent
+    pub fn last(&self) -> Option<&PathSegment> {
+        self.segments.last()
+    }
+
+    /// Append a segment
+    pub fn push(&mut self, segment: PathSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Remove the last segment
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+}
+
+impl FromIterator<PathSegment> for ValuePath {
+    fn from_iter<I: IntoIterator<Item = PathSegment>>(iter: I) -> Self {
+        Self {
+            segments: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for ValuePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 && matches!(segment, PathSegment::Key(_)) {
+                write!(f, ".")?;
+            }
+            match segment {
+                PathSegment::Key(key) if needs_quotes(key) => write!(f, "{}", quote(key))?,
+                PathSegment::Key(key) => write!(f, "{}", key)?,
+                PathSegment::Index(n) => write!(f, "[{}]", n)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a path string into its segments
+///
+/// Each segment is paired with its 1-based column in the path, which is
+/// reported in lookup errors.
+pub fn parse_path(path: &str) -> Result<Vec<(PathSegment, usize)>, Error> {
+    let mut segments = Vec::new();
+    let mut chars = path.char_indices().peekable();
+    let mut expect_key = true;
+
+    while let Some(&(start, ch)) = chars.peek() {
+        let column = path[..start].chars().count() + 1;
+        match ch {
+            '[' => {
+                chars.next();
+                let mut digits = String::new();
+                while let Some(&(_, d)) = chars.peek() {
+                    if d == ']' {
+                        break;
+                    }
+                    digits.push(d);
+                    chars.next();
+                }
+                if chars.next().is_none() {
+                    return Err(Error::path(
+                        ErrorKind::InvalidPath,
+                        column,
+                        format!("Unterminated '[' in path '{}'", path),
+                    ));
+                }
+                let index = digits.parse::<usize>().map_err(|_| {
+                    Error::path(
+                        ErrorKind::InvalidPath,
+                        column,
+                        format!("Invalid array index '{}' in path '{}'", digits, path),
+                    )
+                })?;
+                segments.push((PathSegment::Index(index), column));
+                expect_key = false;
+            }
+            '.' if !expect_key => {
+                chars.next();
+                expect_key = true;
+            }
+            '"' if expect_key => {
+                chars.next();
+                let mut raw = String::new();
+                let mut closed = false;
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => {
+                            raw.push(c);
+                            if let Some((_, escaped)) = chars.next() {
+                                raw.push(escaped);
+                            }
+                        }
+                        _ => raw.push(c),
+                    }
+                }
+                if !closed {
+                    return Err(Error::path(
+                        ErrorKind::InvalidPath,
+                        column,
+                        format!("Unterminated quoted key in path '{}'", path),
+                    ));
+                }
+                let key = unescape(&raw).map_err(|e| {
+                    Error::path(
+                        ErrorKind::InvalidPath,
+                        column,
+                        format!("{} in path '{}'", e.message, path),
+                    )
+                })?;
+                segments.push((PathSegment::Key(key.into_owned()), column));
+                expect_key = false;
+            }
+            _ if expect_key => {
+                let mut key = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c == '.' || c == '[' {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err(Error::path(
+                        ErrorKind::InvalidPath,
+                        column,
+                        format!("Empty key in path '{}'", path),
+                    ));
+                }
+                segments.push((PathSegment::Key(key), column));
+                expect_key = false;
+            }
+            _ => {
+                return Err(Error::path(
+                    ErrorKind::InvalidPath,
+                    column,
+                    format!("Expected '.' or '[' in path '{}', found '{}'", path, ch),
+                ))
+            }
+        }
+    }
+
+    if expect_key && !segments.is_empty() {
+        return Err(Error::path(
+            ErrorKind::InvalidPath,
+            path.chars().count() + 1,
+            format!("Path '{}' ends with '.'", path),
+        ));
+    }
+
+    Ok(segments)
+}
+
+/// Check if a key has to be quoted to be read back from a path
+fn needs_quotes(key: &str) -> bool {
+    key.is_empty() || key.starts_with('"') || key.contains(['.', '['])
+}
+
+impl Value {
+    /// Look up a value by path, e.g. `app.server.port` or `users[3].name`
+    ///
+    /// Unlike `value["a"]["b"]`, a missing field, out-of-bounds position or
+    /// wrongly-typed intermediate value is an error naming the segment.
+    ///
+    /// ```
+    /// use gbln::{parse, ErrorKind};
+    ///
+    /// let value = parse("app{server{port<u16>(8080)}}").unwrap();
+    /// assert_eq!(value.get_path("app.server.port").unwrap().as_u16(), Some(8080));
+    ///
+    /// let err = value.get_path("app.server.host").unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::PathNotFound);
+    /// ```
+    pub fn get_path(&self, path: &str) -> Result<&Value, Error> {
+        let segments = parse_path(path)?;
+        let mut current = self;
+
+        for (i, (segment, column)) in segments.iter().enumerate() {
+            current = step(current, segment).map_err(|kind| {
+                lookup_error(kind, current, path, &segments[..i], segment, *column)
+            })?;
+        }
+
+        Ok(current)
+    }
+
+    /// Mutable variant of [`Value::get_path`]
+    pub fn get_path_mut(&mut self, path: &str) -> Result<&mut Value, Error> {
+        let segments = parse_path(path)?;
+        let mut current = self;
+
+        for (i, (segment, column)) in segments.iter().enumerate() {
+            if let Err(kind) = step(current, segment) {
+                return Err(lookup_error(
+                    kind,
+                    current,
+                    path,
+                    &segments[..i],
+                    segment,
+                    *column,
+                ));
+            }
+            current = step_mut(current, segment).expect("segment checked above");
+        }
+
+        Ok(current)
+    }
+
+    /// Set the value at a path, returning the previous value if any
+    ///
+    /// All intermediate segments must exist. The final segment may name a
+    /// new field of an existing object, which is appended; an array position
+    /// must already exist.
+    pub fn set_path(
+        &mut self,
+        path: &str,
+        value: impl Into<Value>,
+    ) -> Result<Option<Value>, Error> {
+        let value = value.into();
+        let segments = parse_path(path)?;
+        let Some(((last, column), parents)) = segments.split_last() else {
+            return Ok(Some(std::mem::replace(self, value)));
+        };
+
+        let mut current = self;
+        for (i, (segment, col)) in parents.iter().enumerate() {
+            if let Err(kind) = step(current, segment) {
+                return Err(lookup_error(
+                    kind,
+                    current,
+                    path,
+                    &parents[..i],
+                    segment,
+                    *col,
+                ));
+            }
+            current = step_mut(current, segment).expect("segment checked above");
+        }
+
+        match (current, last) {
+            (Value::Object(map), PathSegment::Key(key)) => Ok(map.insert(key.clone(), value)),
+            (current, PathSegment::Index(_)) if current.is_array() => {
+                if let Err(kind) = step(current, last) {
+                    return Err(lookup_error(kind, current, path, parents, last, *column));
+                }
+                let slot = step_mut(current, last).expect("segment checked above");
+                Ok(Some(std::mem::replace(slot, value)))
+            }
+            (current, _) => Err(lookup_error(
+                ErrorKind::TypeMismatch,
+                current,
+                path,
+                parents,
+                last,
+                *column,
+            )),
+        }
+    }
+
+    /// Look up a value by path, returning `None` on any failure
+    pub fn pointer(&self, path: &str) -> Option<&Value> {
+        self.get_path(path).ok()
+    }
+}
+
+/// Take one step, reporting why it failed
+fn step<'v>(value: &'v Value, segment: &PathSegment) -> Result<&'v Value, ErrorKind> {
+    match (value, segment) {
+        (Value::Object(map), PathSegment::Key(key)) => map.get(key).ok_or(ErrorKind::PathNotFound),
+        (Value::Array(arr), PathSegment::Index(i)) => arr.get(*i).ok_or(ErrorKind::PathNotFound),
+        _ => Err(ErrorKind::TypeMismatch),
+    }
+}
+
+fn step_mut<'v>(value: &'v mut Value, segment: &PathSegment) -> Option<&'v mut Value> {
+    match (value, segment) {
+        (Value::Object(map), PathSegment::Key(key)) => map.get_mut(key),
+        (Value::Array(arr), PathSegment::Index(i)) => arr.get_mut(*i),
+        _ => None,
+    }
+}
+
+/// Build the error for a failed step at `segment`, whose parent is `parent`
+fn lookup_error(
+    kind: ErrorKind,
+    parent: &Value,
+    path: &str,
+    walked: &[(PathSegment, usize)],
+    segment: &PathSegment,
+    column: usize,
+) -> Error {
+    let location = if walked.is_empty() {
+        "root".to_string()
+    } else {
+        format!("'{}'", format_path(walked))
+    };
+
+    let message = match (&kind, segment) {
+        (ErrorKind::PathNotFound, PathSegment::Key(key)) => {
+            format!("Path '{}': key '{}' not found in {}", path, key, location)
+        }
+        (ErrorKind::PathNotFound, PathSegment::Index(i)) => format!(
+            "Path '{}': index {} out of bounds in {} (length {})",
+            path,
+            i,
+            location,
+            parent.as_array().map_or(0, Vec::len)
+        ),
+        (_, PathSegment::Key(key)) => format!(
+            "Path '{}': cannot look up key '{}', {} is {} not object",
+            path,
+            key,
+            location,
+            parent.type_name()
+        ),
+        (_, PathSegment::Index(i)) => format!(
+            "Path '{}': cannot look up index {}, {} is {} not array",
+            path,
+            i,
+            location,
+            parent.type_name()
+        ),
+    };
+
+    let error = Error::path(kind, column, message);
+    match (parent, segment) {
+        (Value::Object(map), PathSegment::Key(_)) if !map.is_empty() => {
+            error.with_suggestion(format!("available keys: {}", key_list(map)))
+        }
+        _ => error,
+    }
+}
+
+/// Render parsed segments back into path syntax
+fn format_path(segments: &[(PathSegment, usize)]) -> String {
+    segments
+        .iter()
+        .map(|(segment, _)| segment.clone())
+        .collect::<ValuePath>()
+        .to_string()
+}
+
+fn key_list(map: &Map) -> String {
+    map.keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
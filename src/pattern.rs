@@ -0,0 +1,513 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! GBLN String Patterns
+//!
+//! Regular expressions for `sN:/regex/` hints. The supported syntax is the
+//! common subset:
+//!
+//! - literals, `.` (any character), and `\` to escape a metacharacter
+//! - classes: `[a-z0-9-]`, `[^,]`, and `\d \w \s` (`\D \W \S` negated)
+//! - groups `(...)` and alternation `a|b`
+//! - quantifiers `* + ? {n} {n,} {n,m}`
+//! - anchors `^` and `$`
+//!
+//! A pattern must match the whole string. Matching simulates the compiled
+//! automaton, so it takes time proportional to the input length times the
+//! program size. Repeat counts are capped at 1000 and compilation work at
+//! 10 000 steps, which keeps both the program and compiling it small.
+
+use crate::error::{Error, ErrorKind};
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest compiled program, and most compilation steps, bounding what
+/// `{n,m}` may expand to
+const MAX_PROGRAM: usize = 10_000;
+
+/// Largest count allowed in `{n,m}`
+const MAX_REPEAT: u32 = 1000;
+
+/// A compiled string pattern
+///
+/// Two patterns are equal if they were written the same way.
+#[derive(Clone)]
+pub struct Pattern {
+    source: String,
+    program: Vec<Inst>,
+}
+
+impl Pattern {
+    /// The pattern as written (without the enclosing slashes)
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Check whether the whole of `s` matches
+    pub fn is_match(&self, s: &str) -> bool {
+        let mut seen = vec![usize::MAX; self.program.len()];
+        let mut current = Vec::new();
+        let mut next = Vec::new();
+        let mut chars = s.chars().peekable();
+        let mut step = 0;
+
+        self.add_thread(
+            &mut current,
+            &mut seen,
+            0,
+            step,
+            true,
+            chars.peek().is_none(),
+        );
+        while let Some(c) = chars.next() {
+            step += 1;
+            let at_end = chars.peek().is_none();
+            for &pc in &current {
+                let advance = match &self.program[pc] {
+                    Inst::Char(set) => set.matches(c),
+                    Inst::Any => true,
+                    _ => false,
+                };
+                if advance {
+                    self.add_thread(&mut next, &mut seen, pc + 1, step, false, at_end);
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+            if current.is_empty() {
+                return false;
+            }
+        }
+
+        current
+            .iter()
+            .any(|&pc| matches!(self.program[pc], Inst::Match))
+    }
+
+    /// Add `pc` and everything reachable from it without consuming input
+    fn add_thread(
+        &self,
+        list: &mut Vec<usize>,
+        seen: &mut [usize],
+        pc: usize,
+        step: usize,
+        at_start: bool,
+        at_end: bool,
+    ) {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if seen[pc] == step {
+                continue;
+            }
+            seen[pc] = step;
+            match self.program[pc] {
+                Inst::Jump(target) => stack.push(target),
+                Inst::Split(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                Inst::Start if at_start => stack.push(pc + 1),
+                Inst::End if at_end => stack.push(pc + 1),
+                Inst::Start | Inst::End => {}
+                Inst::Char(_) | Inst::Any | Inst::Match => list.push(pc),
+            }
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    /// Compile a pattern (written without the enclosing slashes)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            Error::value(
+                ErrorKind::InvalidTypeHint,
+                format!("Invalid pattern /{}/: {}", s, reason),
+            )
+        };
+
+        let mut parser = PatternParser {
+            chars: s.chars().collect(),
+            pos: 0,
+        };
+        let node = parser.alternation().map_err(invalid)?;
+        if parser.pos < parser.chars.len() {
+            return Err(invalid("unmatched ')'"));
+        }
+
+        let mut program = Vec::new();
+        let mut steps = 0;
+        compile(&node, &mut program, &mut steps).map_err(invalid)?;
+        program.push(Inst::Match);
+
+        Ok(Pattern {
+            source: s.to_string(),
+            program,
+        })
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Pattern {}
+
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pattern(/{}/)", self.source)
+    }
+}
+
+impl fmt::Display for Pattern {
+    /// Formats the pattern as written in a hint (`/[a-z]+/`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/", self.source)
+    }
+}
+
+/// Parsed pattern syntax
+enum Node {
+    Empty,
+    Char(CharSet),
+    Any,
+    Start,
+    End,
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+/// Compiled automaton instruction
+#[derive(Clone)]
+enum Inst {
+    Char(CharSet),
+    Any,
+    Start,
+    End,
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+/// A set of characters: a class, an escape such as `\d`, or a literal
+#[derive(Clone)]
+struct CharSet {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+#[derive(Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl CharSet {
+    fn single(item: ClassItem) -> Self {
+        CharSet {
+            negated: false,
+            items: vec![item],
+        }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        let found = self.items.iter().any(|item| match *item {
+            ClassItem::Range(lo, hi) => (lo..=hi).contains(&c),
+            ClassItem::Digit(negated) => c.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => (c.is_ascii_alphanumeric() || c == '_') != negated,
+            ClassItem::Space(negated) => c.is_whitespace() != negated,
+        });
+        found != self.negated
+    }
+}
+
+/// Recursive descent parser for the pattern syntax
+struct PatternParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PatternParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// `concat ('|' concat)*`
+    fn alternation(&mut self) -> Result<Node, &'static str> {
+        let mut branches = vec![self.concat()?];
+        while self.eat('|') {
+            branches.push(self.concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap_or(Node::Empty)
+        } else {
+            Node::Alternation(branches)
+        })
+    }
+
+    /// Quantified atoms up to `|`, `)` or the end
+    fn concat(&mut self) -> Result<Node, &'static str> {
+        let mut items = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            match self.quantified(atom)? {
+                Node::Empty => {}
+                item => items.push(item),
+            }
+        }
+        Ok(match items.len() {
+            0 => Node::Empty,
+            1 => items.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(items),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, &'static str> {
+        match self.next() {
+            Some('(') => {
+                let inner = self.alternation()?;
+                if !self.eat(')') {
+                    return Err("unclosed '('");
+                }
+                Ok(inner)
+            }
+            Some('[') => self.class().map(Node::Char),
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('\\') => self.escape().map(|item| Node::Char(CharSet::single(item))),
+            Some('*' | '+' | '?' | '{') => Err("quantifier without anything to repeat"),
+            Some(c) => Ok(Node::Char(CharSet::single(ClassItem::Range(c, c)))),
+            None => Err("unexpected end"),
+        }
+    }
+
+    /// The character after a `\`
+    fn escape(&mut self) -> Result<ClassItem, &'static str> {
+        let item = match self.next().ok_or("trailing '\\'")? {
+            'd' => ClassItem::Digit(false),
+            'D' => ClassItem::Digit(true),
+            'w' => ClassItem::Word(false),
+            'W' => ClassItem::Word(true),
+            's' => ClassItem::Space(false),
+            'S' => ClassItem::Space(true),
+            'n' => ClassItem::Range('\n', '\n'),
+            't' => ClassItem::Range('\t', '\t'),
+            c if c.is_ascii_alphanumeric() => return Err("unknown escape"),
+            c => ClassItem::Range(c, c),
+        };
+        Ok(item)
+    }
+
+    /// A `[...]` class (after the `[`)
+    fn class(&mut self) -> Result<CharSet, &'static str> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+
+        loop {
+            let c = self.next().ok_or("unclosed '['")?;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+
+            let lo = match c {
+                '\\' => match self.escape()? {
+                    ClassItem::Range(c, _) => c,
+                    item => {
+                        items.push(item);
+                        continue;
+                    }
+                },
+                c => c,
+            };
+
+            // `a-z` range, unless the `-` is the last character of the class
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&c| c != ']') {
+                self.pos += 1;
+                let hi = match self.next().ok_or("unclosed '['")? {
+                    '\\' => match self.escape()? {
+                        ClassItem::Range(c, _) => c,
+                        _ => return Err("class escape as range bound"),
+                    },
+                    c => c,
+                };
+                if hi < lo {
+                    return Err("range out of order");
+                }
+                items.push(ClassItem::Range(lo, hi));
+            } else {
+                items.push(ClassItem::Range(lo, lo));
+            }
+        }
+
+        Ok(CharSet { negated, items })
+    }
+
+    /// Apply any quantifiers following an atom
+    fn quantified(&mut self, mut node: Node) -> Result<Node, &'static str> {
+        loop {
+            let (min, max) = if self.eat('*') {
+                (0, None)
+            } else if self.eat('+') {
+                (1, None)
+            } else if self.eat('?') {
+                (0, Some(1))
+            } else if self.eat('{') {
+                self.counted()?
+            } else {
+                return Ok(node);
+            };
+            // Repeating nothing still matches nothing
+            if matches!(node, Node::Empty) {
+                continue;
+            }
+            node = Node::Repeat {
+                node: Box::new(node),
+                min,
+                max,
+            };
+        }
+    }
+
+    /// `n}`, `n,}` or `n,m}` (after the `{`)
+    fn counted(&mut self) -> Result<(u32, Option<u32>), &'static str> {
+        let min = self.number()?.ok_or("expected count in '{}'")?;
+        let max = if self.eat(',') {
+            self.number()?
+        } else {
+            Some(min)
+        };
+        if !self.eat('}') {
+            return Err("unclosed '{'");
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err("repeat count out of order");
+        }
+        if max.unwrap_or(min) > MAX_REPEAT {
+            return Err("repeat count above 1000");
+        }
+        Ok((min, max))
+    }
+
+    fn number(&mut self) -> Result<Option<u32>, &'static str> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        digits
+            .parse()
+            .map(Some)
+            .map_err(|_| "repeat count too large")
+    }
+}
+
+/// Append the instructions for `node` to `program`
+///
+/// `steps` counts calls, so that repeats of nodes compiling to few or no
+/// instructions are bounded as well.
+fn compile(node: &Node, program: &mut Vec<Inst>, steps: &mut usize) -> Result<(), &'static str> {
+    *steps += 1;
+    if program.len() > MAX_PROGRAM || *steps > MAX_PROGRAM {
+        return Err("pattern too large");
+    }
+
+    match node {
+        Node::Empty => {}
+        Node::Char(set) => program.push(Inst::Char(set.clone())),
+        Node::Any => program.push(Inst::Any),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::Concat(items) => {
+            for item in items {
+                compile(item, program, steps)?;
+            }
+        }
+        Node::Alternation(branches) => {
+            let mut exits = Vec::new();
+            for (i, branch) in branches.iter().enumerate() {
+                if i + 1 < branches.len() {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(branch, program, steps)?;
+                    exits.push(program.len());
+                    program.push(Inst::Jump(0));
+                    let next = program.len();
+                    program[split] = Inst::Split(split + 1, next);
+                } else {
+                    compile(branch, program, steps)?;
+                }
+            }
+            let end = program.len();
+            for exit in exits {
+                program[exit] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                compile(node, program, steps)?;
+            }
+            match max {
+                None => {
+                    // loop: split(body, out); body; jump loop
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(node, program, steps)?;
+                    program.push(Inst::Jump(split));
+                    let out = program.len();
+                    program[split] = Inst::Split(split + 1, out);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(0, 0));
+                        compile(node, program, steps)?;
+                    }
+                    let out = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, out);
+                    }
+                }
+            }
+        }
+    }
+
+    if program.len() > MAX_PROGRAM {
+        return Err("pattern too large");
+    }
+    Ok(())
+}
@@ -4,106 +4,382 @@
 //! GBLN Serializer
 //!
 //! Converts Value to GBLN string format (compact and pretty).
-
+//!
+//! Floats are written with the shortest digits that parse back to the same
+//! `f32`/`f64` bits, in plain decimal form (`0.1`, `-0`, `16777216`) unless
+//! exponent notation is shorter (`1e300`, `5e-324`). NaN and infinities are
+//! written `nan`, `inf` and `-inf`, always with a type hint.
+
+use crate::borrowed::Value as BorrowedValue;
+use crate::config::{Dialect, GblnConfig, ParseOptions};
+use crate::encoding::encode_base64;
+use crate::error::{Error, ErrorKind};
+use crate::escape::{escape, quote};
+use crate::lexer::is_bare_token;
+use crate::map::Map;
+use crate::parser::infer_scalar;
+use crate::types::{Declaration, LenRange, TypeHint};
 use crate::value::Value;
+use std::borrow::Cow;
+
+/// Formatting options threaded through the serialiser
+struct Format {
+    pretty: bool,
+    indent_width: usize,
+    sort_keys: bool,
+    reinfer_types: bool,
+    /// Write field doc comments (pretty output only)
+    doc_comments: bool,
+}
+
+impl Format {
+    fn new(config: &GblnConfig) -> Self {
+        Format {
+            pretty: !config.mini_mode,
+            indent_width: config.indent,
+            sort_keys: config.sort_keys,
+            reinfer_types: config.reinfer_types,
+            doc_comments: !config.mini_mode && !config.strip_comments,
+        }
+    }
+
+    /// Indentation for the given nesting depth
+    fn pad(&self, depth: usize) -> String {
+        " ".repeat(self.indent_width * depth)
+    }
+}
 
 /// Serialise a Value to compact GBLN string
+///
+/// Formatted as by [`GblnConfig::io_format`].
 pub fn to_string(value: &Value) -> String {
-    serialize_value(value, false, 0)
+    serialize_value(value, &Format::new(&GblnConfig::io_format()), 0)
 }
 
 /// Serialise a Value to formatted GBLN string with indentation
+///
+/// Formatted as by [`GblnConfig::development`], which keeps comments, so
+/// field doc comments are written as `:||` lines before their field. Use
+/// [`to_string_with_config`] with `strip_comments(true)` to leave them out.
 pub fn to_string_pretty(value: &Value) -> String {
-    serialize_value(value, true, 0)
+    serialize_value(value, &Format::new(&GblnConfig::development()), 0)
+}
+
+/// Serialise a Value according to a configuration
+///
+/// Honours `mini_mode`, `indent`, `sort_keys`, `reinfer_types`,
+/// `allow_non_finite` and `strip_comments`. Object fields are emitted in
+/// insertion order unless `sort_keys` is set. Field doc comments are written
+/// in pretty output unless `strip_comments` is set; compact output has no
+/// room for them, as a comment runs to the end of the line.
+///
+/// # Errors
+///
+/// Fails with `ErrorKind::NonFiniteFloat` if `allow_non_finite` is false
+/// and the value contains a NaN or infinite float.
+///
+/// # Examples
+///
+/// ```
+/// use gbln::{parse, to_string_with_config, GblnConfig};
+///
+/// let value = parse("{b<u8>(2)a<u8>(1)}").unwrap();
+///
+/// let config = GblnConfig::new().sort_keys(true);
+/// assert_eq!(to_string_with_config(&value, &config).unwrap(), "{a<u8>(1)b<u8>(2)}");
+/// ```
+pub fn to_string_with_config(value: &Value, config: &GblnConfig) -> Result<String, Error> {
+    if !config.allow_non_finite {
+        let non_finite = value.walk().find(|(_, v)| match v {
+            Value::F32(n) => !n.is_finite(),
+            Value::F64(n) => !n.is_finite(),
+            _ => false,
+        });
+        if let Some((path, v)) = non_finite {
+            return Err(Error::value(
+                ErrorKind::NonFiniteFloat,
+                format!(
+                    "Non-finite float '{}' at '{}' is not allowed",
+                    scalar_text(v).unwrap_or_default(),
+                    path
+                ),
+            ));
+        }
+    }
+
+    Ok(serialize_value(value, &Format::new(config), 0))
 }
 
 /// Internal serialisation with formatting control
-fn serialize_value(value: &Value, pretty: bool, indent: usize) -> String {
+fn serialize_value(value: &Value, fmt: &Format, indent: usize) -> String {
     match value {
-        Value::I8(n) => format!("<i8>({})", n),
-        Value::I16(n) => format!("<i16>({})", n),
-        Value::I32(n) => format!("<i32>({})", n),
-        Value::I64(n) => format!("<i64>({})", n),
-        Value::U8(n) => format!("<u8>({})", n),
-        Value::U16(n) => format!("<u16>({})", n),
-        Value::U32(n) => format!("<u32>({})", n),
-        Value::U64(n) => format!("<u64>({})", n),
-        Value::F32(n) => format!("<f32>({})", n),
-        Value::F64(n) => format!("<f64>({})", n),
-        Value::Str(s) => {
-            // Infer string type from length
-            let len = s.chars().count();
-            let type_hint = infer_string_type(len);
-            format!("<{}>({})", type_hint, s)
-        }
-        Value::Bool(b) => {
-            let val = if *b { "t" } else { "f" };
-            format!("<b>({})", val)
-        }
-        Value::Null => "<n>()".to_string(),
-        Value::Object(map) => serialize_object(map, pretty, indent),
-        Value::Array(arr) => serialize_array(arr, pretty, indent),
+        Value::Object(map) => serialize_object(map, fmt, indent),
+        Value::Array(arr) => serialize_array(arr, fmt, indent),
+        _ => serialize_typed_value(value),
     }
 }
 
 /// Serialise an object
-fn serialize_object(
-    map: &std::collections::HashMap<String, Value>,
-    pretty: bool,
-    indent: usize,
-) -> String {
+///
+/// Fields keep the form they were declared in (see [`Map::declaration`]) as
+/// long as the current value can still be written that way; otherwise, or
+/// with `reinfer_types`, the type is inferred from the value.
+fn serialize_object(map: &Map, fmt: &Format, indent: usize) -> String {
     if map.is_empty() {
         return "{}".to_string();
     }
 
     let mut result = String::new();
 
-    // Collect and sort keys for deterministic output
-    let mut keys: Vec<_> = map.keys().collect();
-    keys.sort();
-
-    for (i, key) in keys.iter().enumerate() {
-        let value = &map[*key];
+    // Insertion order by default, sorted keys for canonical output
+    let mut fields: Vec<_> = map.fields().collect();
+    if fmt.sort_keys {
+        fields.sort_by(|a, b| a.0.cmp(b.0));
+    }
 
-        if pretty && i > 0 {
+    for (i, (key, value, meta)) in fields.into_iter().enumerate() {
+        if fmt.pretty && i > 0 {
             result.push('\n');
-            result.push_str(&"  ".repeat(indent + 1));
+            result.push_str(&fmt.pad(indent + 1));
+        }
+
+        if let Some(doc) = meta.doc.as_deref().filter(|_| fmt.doc_comments) {
+            result.push_str(&doc_comment(doc, &fmt.pad(indent + 1)));
+        }
+
+        // Array length bounds are kept while the array still fits them
+        let (declared, len) = match &meta.declared {
+            Some(Declaration::Bounded(inner, len)) => {
+                let fits = matches!(value, Value::Array(arr) if len.contains(arr.len()));
+                (Some(&**inner), Some(len).filter(|_| fits))
+            }
+            declared => (declared.as_ref(), None),
+        };
+
+        // Re-emit the declared form if the value still fits it
+        if !fmt.reinfer_types {
+            if let Some(declared) = declared.and_then(|d| as_declared(d, value, len)) {
+                result.push_str(&key_text(key));
+                result.push_str(&declared);
+                continue;
+            }
         }
 
         // Determine how to serialize based on value type
         match value {
             Value::Object(inner_map) => {
                 // Nested object: key{...}
-                result.push_str(key);
-                result.push_str(&serialize_object(inner_map, pretty, indent + 1));
+                result.push_str(&key_text(key));
+                result.push_str(&serialize_object(inner_map, fmt, indent + 1));
             }
             Value::Array(arr) => {
-                // Array field: key[...] (NO type hint in objects)
-                result.push_str(key);
-                result.push_str(&serialize_array_in_object(arr, pretty, indent + 1));
+                // Records sharing one shape: key<{col<type> ...}>[(...) ...]
+                // Otherwise: key[...] (NO type hint in objects)
+                result.push_str(&key_text(key));
+                let (declared, len) = if fmt.reinfer_types {
+                    (None, None)
+                } else {
+                    (declared, len)
+                };
+                match serialize_table(arr, declared, len, fmt, indent + 1) {
+                    Some(table) => result.push_str(&table),
+                    None => {
+                        if let Some(len) = len {
+                            result.push_str(&format!("<;{}>", len));
+                        }
+                        result.push_str(&serialize_array_in_object(arr, fmt, indent + 1));
+                    }
+                }
             }
             _ => {
                 // Typed single value: key<type>(value)
-                result.push_str(key);
-                result.push_str(&serialize_typed_value(value, pretty, indent));
+                result.push_str(&key_text(key));
+                result.push_str(&serialize_typed_value(value));
             }
         }
     }
 
-    if pretty {
+    if fmt.pretty {
         format!(
             "{{\n{}{}\n{}}}",
-            "  ".repeat(indent + 1),
+            fmt.pad(indent + 1),
             result,
-            "  ".repeat(indent)
+            fmt.pad(indent)
         )
     } else {
         format!("{{{}}}", result)
     }
 }
 
+/// Write a doc comment as `:||` lines, each followed by a newline and the
+/// padding of the field it documents
+fn doc_comment(doc: &str, pad: &str) -> String {
+    doc.split('\n')
+        .map(|line| match line {
+            "" => format!(":||\n{}", pad),
+            line => format!(":|| {}\n{}", line, pad),
+        })
+        .collect()
+}
+
+/// Serialise an array of records as a table, if every row fits one schema
+///
+/// Uses the declared schema if the rows still fit it, otherwise infers one
+/// from the rows. Returns `None` unless every row is an object with the
+/// same scalar fields and every cell reads back unchanged.
+fn serialize_table(
+    arr: &[Value],
+    declared: Option<&Declaration>,
+    len: Option<&LenRange>,
+    fmt: &Format,
+    indent: usize,
+) -> Option<String> {
+    if let Some(Declaration::Table(columns)) = declared {
+        if let Some(table) = table_text(columns, arr, len, fmt, indent) {
+            return Some(table);
+        }
+    }
+
+    let columns = infer_table_columns(arr, fmt)?;
+    table_text(&columns, arr, len, fmt, indent)
+}
+
+/// Write `rows` under the given columns, if every cell round-trips
+fn table_text(
+    columns: &[(String, TypeHint)],
+    rows: &[Value],
+    len: Option<&LenRange>,
+    fmt: &Format,
+    indent: usize,
+) -> Option<String> {
+    let mut lines = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let Value::Object(map) = row else {
+            return None;
+        };
+        if map.len() != columns.len() {
+            return None;
+        }
+
+        let cells = columns
+            .iter()
+            .map(|(name, hint)| {
+                let value = map.get(name.as_str())?;
+                let text = element_text(value).filter(|t| is_bare_token(t))?;
+                fits_hint(hint, &text, value).then_some(text)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        lines.push(format!("({})", cells.join(" ")));
+    }
+
+    let separator = if fmt.pretty { " " } else { "" };
+    let schema: Vec<String> = columns
+        .iter()
+        .map(|(name, hint)| format!("{}<{}>", key_text(name), hint.as_str()))
+        .collect();
+    let schema = format!("<{{{}}}{}>", schema.join(separator), len_suffix(len));
+
+    if fmt.pretty && !lines.is_empty() {
+        let pad = format!("\n{}", fmt.pad(indent + 1));
+        Some(format!(
+            "{}[{}{}\n{}]",
+            schema,
+            pad,
+            lines.join(&pad),
+            fmt.pad(indent)
+        ))
+    } else {
+        Some(format!("{}[{}]", schema, lines.concat()))
+    }
+}
+
+/// Infer table columns from rows that are objects with the same scalar fields
+///
+/// A column keeps the hint its cells were declared with if they all agree;
+/// otherwise the hint is inferred as for typed arrays.
+fn infer_table_columns(rows: &[Value], fmt: &Format) -> Option<Vec<(String, TypeHint)>> {
+    let maps = rows
+        .iter()
+        .map(|row| match row {
+            Value::Object(map) if !map.is_empty() => Some(map),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let first = maps.first()?;
+
+    let mut names: Vec<&String> = first.keys().collect();
+    if fmt.sort_keys {
+        names.sort();
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let cells = maps
+                .iter()
+                .map(|map| map.get(name.as_str()).filter(|v| is_simple_type(v)))
+                .collect::<Option<Vec<_>>>()?;
+
+            let declared = maps
+                .iter()
+                .map(|map| match map.declaration(name.as_str()) {
+                    Some(Declaration::Typed(hint)) if !fmt.reinfer_types => Some(hint),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .filter(|hints| hints.iter().all(|h| *h == hints[0]));
+
+            let hint = match declared {
+                Some(hints) => hints[0].clone(),
+                None => column_hint(&cells)?,
+            };
+            Some((name.clone(), hint))
+        })
+        .collect()
+}
+
+/// Infer the hint for a table column from its cells
+fn column_hint(cells: &[&Value]) -> Option<TypeHint> {
+    let Some(first) = cells.iter().find(|v| !v.is_null()) else {
+        return Some(TypeHint::Null);
+    };
+    if !cells.iter().all(|v| v.is_null() || same_type(v, first)) {
+        return None;
+    }
+
+    let hint = match first {
+        Value::Str(_) => {
+            let max_len = cells
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.chars().count())
+                .max()
+                .unwrap_or(0);
+            infer_string_type(max_len)
+        }
+        Value::Decimal(_) => {
+            let decimals: Vec<_> = cells.iter().filter_map(|v| v.as_decimal()).collect();
+            let scale = decimals[0].scale();
+            if decimals.iter().any(|d| d.scale() != scale) {
+                return None;
+            }
+            let digits = decimals.iter().map(|d| d.digits()).max().unwrap_or(0);
+            format!("dec:{}.{}", digits.max(scale), scale)
+        }
+        _ => get_type_hint(first),
+    };
+
+    let hint = TypeHint::from_str(&hint).ok()?;
+    if cells.iter().any(|v| v.is_null()) {
+        Some(TypeHint::Nullable(Box::new(hint)))
+    } else {
+        Some(hint)
+    }
+}
+
 /// Serialise an array in object context (NO type hint, always mixed/object array format)
-fn serialize_array_in_object(arr: &[Value], pretty: bool, indent: usize) -> String {
+fn serialize_array_in_object(arr: &[Value], fmt: &Format, indent: usize) -> String {
     if arr.is_empty() {
         return "[]".to_string();
     }
@@ -112,32 +388,27 @@ fn serialize_array_in_object(arr: &[Value], pretty: bool, indent: usize) -> Stri
 
     for (i, value) in arr.iter().enumerate() {
         if i > 0 {
-            if pretty {
+            if fmt.pretty {
                 result.push('\n');
-                result.push_str(&"  ".repeat(indent + 1));
+                result.push_str(&fmt.pad(indent + 1));
             }
-        } else if pretty {
+        } else if fmt.pretty {
             result.push('\n');
-            result.push_str(&"  ".repeat(indent + 1));
+            result.push_str(&fmt.pad(indent + 1));
         }
 
-        result.push_str(&serialize_value(value, pretty, indent + 1));
+        result.push_str(&serialize_value(value, fmt, indent + 1));
     }
 
-    if pretty {
-        format!(
-            "[\n{}{}\n{}]",
-            "  ".repeat(indent + 1),
-            result,
-            "  ".repeat(indent)
-        )
+    if fmt.pretty {
+        format!("[\n{}{}\n{}]", fmt.pad(indent + 1), result, fmt.pad(indent))
     } else {
         format!("[{}]", result)
     }
 }
 
 /// Serialise an array (top-level, can use typed array format)
-fn serialize_array(arr: &[Value], pretty: bool, indent: usize) -> String {
+fn serialize_array(arr: &[Value], fmt: &Format, indent: usize) -> String {
     if arr.is_empty() {
         return "[]".to_string();
     }
@@ -146,7 +417,23 @@ fn serialize_array(arr: &[Value], pretty: bool, indent: usize) -> String {
     if let Some(first) = arr.first() {
         if is_simple_type(first) && arr.iter().all(|v| same_type(v, first)) {
             // Typed array: type[val1 val2 val3]
-            return serialize_typed_array(arr, pretty);
+            if let Some(typed) = serialize_typed_array(arr) {
+                return typed;
+            }
+        }
+    }
+
+    // Homogeneous apart from nulls: <type?>[val1 null val3]
+    if let Some(first) = arr.iter().find(|v| !v.is_null()) {
+        if is_simple_type(first)
+            && arr.iter().all(|v| v.is_null() || same_type(v, first))
+            && !arr
+                .iter()
+                .any(|v| matches!(v, Value::Str(s) if Dialect::Lenient.is_null(s)))
+        {
+            if let Some(typed) = serialize_typed_array(arr) {
+                return typed;
+            }
         }
     }
 
@@ -155,38 +442,38 @@ fn serialize_array(arr: &[Value], pretty: bool, indent: usize) -> String {
 
     for (i, value) in arr.iter().enumerate() {
         if i > 0 {
-            if pretty {
+            if fmt.pretty {
                 result.push('\n');
-                result.push_str(&"  ".repeat(indent + 1));
+                result.push_str(&fmt.pad(indent + 1));
             }
-        } else if pretty {
+        } else if fmt.pretty {
             result.push('\n');
-            result.push_str(&"  ".repeat(indent + 1));
+            result.push_str(&fmt.pad(indent + 1));
         }
 
-        result.push_str(&serialize_value(value, pretty, indent + 1));
+        result.push_str(&serialize_value(value, fmt, indent + 1));
     }
 
-    if pretty {
-        format!(
-            "[\n{}{}\n{}]",
-            "  ".repeat(indent + 1),
-            result,
-            "  ".repeat(indent)
-        )
+    if fmt.pretty {
+        format!("[\n{}{}\n{}]", fmt.pad(indent + 1), result, fmt.pad(indent))
     } else {
         format!("[{}]", result)
     }
 }
 
 /// Serialise a typed array (homogeneous simple types)
-fn serialize_typed_array(arr: &[Value], _pretty: bool) -> String {
+///
+/// Returns `None` if an element cannot be written as a bare token.
+fn serialize_typed_array(arr: &[Value]) -> Option<String> {
     if arr.is_empty() {
-        return "[]".to_string();
+        return Some("[]".to_string());
     }
 
+    // Nulls only decide whether the hint gets a `?` modifier
+    let first = arr.iter().find(|v| !v.is_null()).unwrap_or(&arr[0]);
+
     // For strings, find the longest to determine type hint
-    let type_hint = if matches!(arr[0], Value::Str(_)) {
+    let mut type_hint = if matches!(first, Value::Str(_)) {
         let max_len = arr
             .iter()
             .filter_map(|v| {
@@ -200,59 +487,219 @@ fn serialize_typed_array(arr: &[Value], _pretty: bool) -> String {
             .unwrap_or(0);
         infer_string_type(max_len)
     } else {
-        get_type_hint(&arr[0])
+        get_type_hint(first)
     };
-
-    let mut values = Vec::new();
-
-    for value in arr {
-        let val_str = match value {
-            Value::I8(n) => n.to_string(),
-            Value::I16(n) => n.to_string(),
-            Value::I32(n) => n.to_string(),
-            Value::I64(n) => n.to_string(),
-            Value::U8(n) => n.to_string(),
-            Value::U16(n) => n.to_string(),
-            Value::U32(n) => n.to_string(),
-            Value::U64(n) => n.to_string(),
-            Value::F32(n) => n.to_string(),
-            Value::F64(n) => n.to_string(),
-            Value::Str(s) => s.clone(),
-            Value::Bool(b) => if *b { "t" } else { "f" }.to_string(),
-            Value::Null => "null".to_string(),
-            _ => continue,
-        };
-        values.push(val_str);
+    if !first.is_null() && arr.iter().any(Value::is_null) {
+        type_hint.push('?');
     }
 
+    let values = arr
+        .iter()
+        .map(|v| element_text(v).filter(|t| is_bare_token(t)))
+        .collect::<Option<Vec<_>>>()?;
+
     // Typed arrays are always compact (no difference between pretty and compact)
-    format!("<{}>[{}]", type_hint, values.join(" "))
+    Some(format!("<{}>[{}]", type_hint, values.join(" ")))
 }
 
 /// Serialise a typed single value (without outer type hint, used in objects)
-fn serialize_typed_value(value: &Value, _pretty: bool, _indent: usize) -> String {
+fn serialize_typed_value(value: &Value) -> String {
+    match scalar_text(value) {
+        Some(text) => format!("<{}>({})", get_type_hint(value), escape(&text)),
+        None => String::new(),
+    }
+}
+
+/// Serialise a field value in its declared form, if it round-trips
+///
+/// Returns `None` if parsing the output would not give back `value`, e.g.
+/// because a string outgrew its declared `sN` or the variant changed.
+fn as_declared(declared: &Declaration, value: &Value, len: Option<&LenRange>) -> Option<String> {
+    match (declared, value) {
+        (Declaration::Typed(hint), _) => {
+            let text = scalar_text(value)?;
+            fits_hint(hint, &text, value).then(|| format!("<{}>({})", hint.as_str(), escape(&text)))
+        }
+        (Declaration::TypedArray(hint), Value::Array(arr)) => {
+            let items = arr
+                .iter()
+                .map(|v| {
+                    let text = element_text(v).filter(|t| is_bare_token(t))?;
+                    fits_hint(hint, &text, v).then_some(text)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!(
+                "<{}{}>[{}]",
+                hint.as_str(),
+                len_suffix(len),
+                items.join(" ")
+            ))
+        }
+        (Declaration::Untyped, Value::Array(arr)) => {
+            let items = arr
+                .iter()
+                .map(|v| {
+                    let text = element_text(v).filter(|t| is_bare_token(t))?;
+                    infers_as(&text, v).then_some(text)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            match len {
+                Some(len) => Some(format!("<;{}>[{}]", len, items.join(" "))),
+                None => Some(format!("[{}]", items.join(" "))),
+            }
+        }
+        (Declaration::Untyped, _) => {
+            let text = scalar_text(value)?;
+            infers_as(&text, value).then(|| format!("({})", escape(&text)))
+        }
+        _ => None,
+    }
+}
+
+/// Length bounds as written at the end of an array hint (`;..16`)
+fn len_suffix(len: Option<&LenRange>) -> String {
+    len.map(|len| format!(";{}", len)).unwrap_or_default()
+}
+
+/// Raw text of a scalar value as written inside `(...)` or a typed array
+fn scalar_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::I8(n) => n.to_string(),
+        Value::I16(n) => n.to_string(),
+        Value::I32(n) => n.to_string(),
+        Value::I64(n) => n.to_string(),
+        Value::I128(n) => n.to_string(),
+        Value::U8(n) => n.to_string(),
+        Value::U16(n) => n.to_string(),
+        Value::U32(n) => n.to_string(),
+        Value::U64(n) => n.to_string(),
+        Value::U128(n) => n.to_string(),
+        Value::F32(n) => format_f32(*n),
+        Value::F64(n) => format_f64(*n),
+        Value::Decimal(d) => d.to_string(),
+        Value::Str(s) => s.clone(),
+        Value::Date(d) => d.to_string(),
+        Value::Time(t) => t.to_string(),
+        Value::DateTime(dt) => dt.to_string(),
+        Value::Timestamp(ts) => ts.to_string(),
+        Value::Bytes(b) => encode_base64(b),
+        Value::Bool(b) => if *b { "t" } else { "f" }.to_string(),
+        Value::Null => String::new(),
+        Value::Object(_) | Value::Array(_) => return None,
+    };
+    Some(text)
+}
+
+/// Shortest text that parses back to the same `f32`
+///
+/// `Display` and `LowerExp` both pick the shortest digits that round-trip;
+/// `Display` never uses an exponent, so whichever form is shorter wins.
+fn format_f32(n: f32) -> String {
+    if n.is_nan() {
+        "nan".to_string()
+    } else {
+        shorter(n.to_string(), format!("{:e}", n))
+    }
+}
+
+/// Shortest text that parses back to the same `f64` (see [`format_f32`])
+fn format_f64(n: f64) -> String {
+    if n.is_nan() {
+        "nan".to_string()
+    } else {
+        shorter(n.to_string(), format!("{:e}", n))
+    }
+}
+
+/// Exponent form if it is strictly shorter than the plain form
+fn shorter(plain: String, exp: String) -> String {
+    if exp.len() < plain.len() {
+        exp
+    } else {
+        plain
+    }
+}
+
+/// Raw text of a scalar array element, where null has to be spelled out
+fn element_text(value: &Value) -> Option<String> {
     match value {
-        Value::I8(n) => format!("<i8>({})", n),
-        Value::I16(n) => format!("<i16>({})", n),
-        Value::I32(n) => format!("<i32>({})", n),
-        Value::I64(n) => format!("<i64>({})", n),
-        Value::U8(n) => format!("<u8>({})", n),
-        Value::U16(n) => format!("<u16>({})", n),
-        Value::U32(n) => format!("<u32>({})", n),
-        Value::U64(n) => format!("<u64>({})", n),
-        Value::F32(n) => format!("<f32>({})", n),
-        Value::F64(n) => format!("<f64>({})", n),
-        Value::Str(s) => {
-            let len = s.chars().count();
-            let type_hint = infer_string_type(len);
-            format!("<{}>({})", type_hint, s)
+        Value::Null => Some("null".to_string()),
+        _ => scalar_text(value),
+    }
+}
+
+/// Check that `text`, written for `value`, parses back to it under `hint`
+/// in every dialect
+///
+/// The serialiser's own spellings read the same in both dialects, except
+/// that the lenient one takes `n` as null under a nullable hint. A hint of
+/// the value's own type without constraints is accepted as is; anything
+/// else is parsed once.
+fn fits_hint(hint: &TypeHint, text: &str, value: &Value) -> bool {
+    match (hint, value) {
+        (TypeHint::Nullable(_), Value::Null) => true,
+        (TypeHint::Nullable(inner), _) => {
+            !Dialect::Lenient.is_null(text) && fits_hint(inner, text, value)
         }
-        Value::Bool(b) => {
-            let val = if *b { "t" } else { "f" };
-            format!("<b>({})", val)
+        (TypeHint::Str(_) | TypeHint::ConstrainedStr(..) | TypeHint::Enum(_), Value::Str(_)) => {
+            hint.check_str(text).is_ok()
         }
-        Value::Null => "<n>()".to_string(),
-        _ => String::new(),
+        (TypeHint::I8, Value::I8(_))
+        | (TypeHint::I16, Value::I16(_))
+        | (TypeHint::I32, Value::I32(_))
+        | (TypeHint::I64, Value::I64(_))
+        | (TypeHint::I128, Value::I128(_))
+        | (TypeHint::U8, Value::U8(_))
+        | (TypeHint::U16, Value::U16(_))
+        | (TypeHint::U32, Value::U32(_))
+        | (TypeHint::U64, Value::U64(_))
+        | (TypeHint::U128, Value::U128(_))
+        | (TypeHint::F32, Value::F32(_))
+        | (TypeHint::F64, Value::F64(_))
+        | (TypeHint::Date, Value::Date(_))
+        | (TypeHint::Time, Value::Time(_))
+        | (TypeHint::DateTime, Value::DateTime(_))
+        | (TypeHint::Timestamp, Value::Timestamp(_))
+        | (TypeHint::Bin(None), Value::Bytes(_))
+        | (TypeHint::Bool, Value::Bool(_))
+        | (TypeHint::Null, Value::Null) => true,
+        _ => hint
+            .parse_value_with_options(text, &ParseOptions::strict())
+            .is_ok_and(|parsed| parsed == *value),
+    }
+}
+
+/// Check that untyped `text`, written for `value`, is inferred back as
+/// `value` in every dialect, with and without alias inference
+///
+/// Numbers, booleans and null are written in canonical spellings, which
+/// both dialects read alike, so one strict inference settles them. A string
+/// must not be a spelling of null or a boolean in the lenient dialect
+/// (`n`, `yes`, `0`, ...), nor look like a number to either dialect.
+fn infers_as(text: &str, value: &Value) -> bool {
+    let Value::Str(_) = value else {
+        return infer_scalar(Cow::Borrowed(text), Dialect::Strict, false).into_owned() == *value;
+    };
+    if Dialect::Lenient.is_null(text) || Dialect::Lenient.parse_bool(text).is_some() {
+        return false;
+    }
+    // Anything either dialect reads as a number parses as f64 (`+5`, `1e5`,
+    // `inf`, `NaN`); the few that still stay strings need a closer look
+    text.parse::<f64>().is_err()
+        || [Dialect::Strict, Dialect::Lenient].iter().all(|&dialect| {
+            matches!(
+                infer_scalar(Cow::Borrowed(text), dialect, true),
+                BorrowedValue::Str(_)
+            )
+        })
+}
+
+/// Key as written in output: bare if possible, quoted otherwise
+fn key_text(key: &str) -> Cow<'_, str> {
+    if is_bare_token(key) {
+        Cow::Borrowed(key)
+    } else {
+        Cow::Owned(quote(key))
     }
 }
 
@@ -273,16 +720,24 @@ fn get_type_hint(value: &Value) -> String {
         Value::I16(_) => "i16".to_string(),
         Value::I32(_) => "i32".to_string(),
         Value::I64(_) => "i64".to_string(),
+        Value::I128(_) => "i128".to_string(),
         Value::U8(_) => "u8".to_string(),
         Value::U16(_) => "u16".to_string(),
         Value::U32(_) => "u32".to_string(),
         Value::U64(_) => "u64".to_string(),
+        Value::U128(_) => "u128".to_string(),
         Value::F32(_) => "f32".to_string(),
         Value::F64(_) => "f64".to_string(),
+        Value::Decimal(d) => format!("dec:{}.{}", d.digits().max(d.scale()), d.scale()),
         Value::Str(s) => {
             let len = s.chars().count();
             infer_string_type(len)
         }
+        Value::Date(_) => "date".to_string(),
+        Value::Time(_) => "time".to_string(),
+        Value::DateTime(_) => "dt".to_string(),
+        Value::Timestamp(_) => "ts".to_string(),
+        Value::Bytes(_) => "bin".to_string(),
         Value::Bool(_) => "b".to_string(),
         Value::Null => "n".to_string(),
         _ => "unknown".to_string(),
@@ -290,6 +745,9 @@ fn get_type_hint(value: &Value) -> String {
 }
 
 /// Infer appropriate string type from length
+///
+/// Lengths are rounded up to a power of two; strings longer than 1024
+/// characters get their exact length.
 fn infer_string_type(len: usize) -> String {
     match len {
         0..=2 => "s2".to_string(),
@@ -301,6 +759,7 @@ fn infer_string_type(len: usize) -> String {
         65..=128 => "s128".to_string(),
         129..=256 => "s256".to_string(),
         257..=512 => "s512".to_string(),
-        _ => "s1024".to_string(),
+        513..=1024 => "s1024".to_string(),
+        _ => format!("s{}", len),
     }
 }
@@ -0,0 +1,356 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! GBLN Temporal Types
+//!
+//! Values of the `date`, `time`, `dt` and `ts` type hints. Each type parses
+//! its strict textual form with `FromStr` and displays in canonical form:
+//!
+//! - `date`: `2024-03-15`
+//! - `time`: `14:30:00` or `14:30:00.25` (up to nanosecond precision)
+//! - `dt`: RFC 3339 with offset, `2024-03-15T14:30:00Z` or
+//!   `2024-03-15T14:30:00+02:00`
+//! - `ts`: Unix time in seconds (`1710513000`) or milliseconds
+//!   (`1710513000250ms`)
+
+use crate::error::{Error, ErrorKind};
+use std::fmt;
+use std::str::FromStr;
+
+/// A calendar date (proleptic Gregorian, years 0000 to 9999)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date, checking that it exists
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, Error> {
+        if year > 9999 {
+            return Err(invalid("date", format!("year {} out of range", year)));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(invalid("date", format!("month {} out of range", month)));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(invalid(
+                "date",
+                format!("day {} out of range for {:04}-{:02}", day, year, month),
+            ));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    /// Parse `YYYY-MM-DD`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let b = s.as_bytes();
+        if b.len() != 10 || !s.is_ascii() || b[4] != b'-' || b[7] != b'-' {
+            return Err(malformed("date", s, "YYYY-MM-DD"));
+        }
+        let field = |range: std::ops::Range<usize>| {
+            digits(&s[range]).ok_or_else(|| malformed("date", s, "YYYY-MM-DD"))
+        };
+        Date::new(field(0..4)? as u16, field(5..7)? as u8, field(8..10)? as u8)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day without offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+impl Time {
+    /// Create a time, checking each component
+    pub fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Result<Self, Error> {
+        if hour > 23 || minute > 59 || second > 59 || nanosecond > 999_999_999 {
+            return Err(invalid(
+                "time",
+                format!(
+                    "{:02}:{:02}:{:02}.{:09} is not a time of day",
+                    hour, minute, second, nanosecond
+                ),
+            ));
+        }
+        Ok(Self {
+            hour,
+            minute,
+            second,
+            nanosecond,
+        })
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Fraction of the second in nanoseconds
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+}
+
+impl FromStr for Time {
+    type Err = Error;
+
+    /// Parse `HH:MM:SS` with an optional fraction of 1 to 9 digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let b = s.as_bytes();
+        if b.len() < 8 || b[2] != b':' || b[5] != b':' || !s.is_ascii() {
+            return Err(malformed("time", s, "HH:MM:SS[.fraction]"));
+        }
+        let field = |range: std::ops::Range<usize>| {
+            digits(&s[range]).ok_or_else(|| malformed("time", s, "HH:MM:SS[.fraction]"))
+        };
+
+        let nanosecond = match &s[8..] {
+            "" => 0,
+            fraction => {
+                let frac = fraction
+                    .strip_prefix('.')
+                    .filter(|f| (1..=9).contains(&f.len()))
+                    .and_then(digits)
+                    .ok_or_else(|| malformed("time", s, "HH:MM:SS[.fraction]"))?;
+                frac * 10u32.pow(10 - fraction.len() as u32)
+            }
+        };
+
+        Time::new(
+            field(0..2)? as u8,
+            field(3..5)? as u8,
+            field(6..8)? as u8,
+            nanosecond,
+        )
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond > 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+/// An RFC 3339 date and time with UTC offset
+///
+/// Comparison is structural (date, then time, then offset), not by instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DateTime {
+    date: Date,
+    time: Time,
+    offset_minutes: i16,
+}
+
+impl DateTime {
+    /// Create a date-time; the offset must be less than 24 hours either way
+    pub fn new(date: Date, time: Time, offset_minutes: i16) -> Result<Self, Error> {
+        if offset_minutes.unsigned_abs() >= 24 * 60 {
+            return Err(invalid(
+                "dt",
+                format!("offset of {} minutes out of range", offset_minutes),
+            ));
+        }
+        Ok(Self {
+            date,
+            time,
+            offset_minutes,
+        })
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// Offset from UTC in minutes (0 for `Z`)
+    pub fn offset_minutes(&self) -> i16 {
+        self.offset_minutes
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = Error;
+
+    /// Parse RFC 3339: `YYYY-MM-DDTHH:MM:SS[.fraction](Z|±HH:MM)`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const FORMAT: &str = "YYYY-MM-DDTHH:MM:SS[.fraction](Z|+HH:MM)";
+        let b = s.as_bytes();
+        if b.len() < 20 || !s.is_ascii() || !matches!(b[10], b'T' | b't') {
+            return Err(malformed("dt", s, FORMAT));
+        }
+        let date = s[..10].parse::<Date>()?;
+        let rest = &s[11..];
+
+        let (time, offset_minutes) = if let Some(time) = rest.strip_suffix(['Z', 'z']) {
+            (time, 0)
+        } else {
+            let split = rest.len().saturating_sub(6);
+            let (time, offset) = rest.split_at(split);
+            let o = offset.as_bytes();
+            if o.len() != 6 || !matches!(o[0], b'+' | b'-') || o[3] != b':' {
+                return Err(malformed("dt", s, FORMAT));
+            }
+            let (hours, minutes) = digits(&offset[1..3])
+                .zip(digits(&offset[4..6]))
+                .filter(|&(_, m)| m < 60)
+                .ok_or_else(|| malformed("dt", s, FORMAT))?;
+            let minutes = (hours * 60 + minutes) as i16;
+            (time, if o[0] == b'-' { -minutes } else { minutes })
+        };
+
+        DateTime::new(date, time.parse::<Time>()?, offset_minutes)
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)?;
+        match self.offset_minutes {
+            0 => write!(f, "Z"),
+            offset => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let abs = offset.unsigned_abs();
+                write!(f, "{}{:02}:{:02}", sign, abs / 60, abs % 60)
+            }
+        }
+    }
+}
+
+/// A point in time as milliseconds since the Unix epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Timestamp {
+    millis: i64,
+}
+
+impl Timestamp {
+    pub fn from_millis(millis: i64) -> Self {
+        Self { millis }
+    }
+
+    /// Returns `None` if the time is not representable in milliseconds
+    pub fn from_secs(secs: i64) -> Option<Self> {
+        secs.checked_mul(1000).map(Self::from_millis)
+    }
+
+    pub fn unix_millis(&self) -> i64 {
+        self.millis
+    }
+
+    /// Whole seconds, rounded towards negative infinity
+    pub fn unix_seconds(&self) -> i64 {
+        self.millis.div_euclid(1000)
+    }
+
+    /// Milliseconds past [`Timestamp::unix_seconds`]
+    pub fn subsec_millis(&self) -> u16 {
+        self.millis.rem_euclid(1000) as u16
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    /// Parse Unix seconds (`1710513000`) or milliseconds (`1710513000250ms`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, millis) = match s.strip_suffix("ms") {
+            Some(number) => (number, true),
+            None => (s, false),
+        };
+        let unsigned = number.strip_prefix('-').unwrap_or(number);
+        if unsigned.is_empty() || !unsigned.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(malformed("ts", s, "Unix seconds or <millis>ms"));
+        }
+        let n = number
+            .parse::<i64>()
+            .map_err(|_| invalid("ts", format!("{} out of range", s)))?;
+        if millis {
+            Ok(Timestamp::from_millis(n))
+        } else {
+            Timestamp::from_secs(n).ok_or_else(|| invalid("ts", format!("{} out of range", s)))
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.millis % 1000 == 0 {
+            write!(f, "{}", self.millis / 1000)
+        } else {
+            write!(f, "{}ms", self.millis)
+        }
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parse a run of ASCII digits (no sign)
+fn digits(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn invalid(hint: &str, message: String) -> Error {
+    Error::value(
+        ErrorKind::TypeMismatch,
+        format!("Invalid {}: {}", hint, message),
+    )
+}
+
+fn malformed(hint: &str, s: &str, format: &str) -> Error {
+    invalid(hint, format!("'{}' (expected {})", s, format))
+}
@@ -5,7 +5,13 @@
 //!
 //! Defines all type hints and their validation bounds.
 
+use crate::config::{Dialect, ParseOptions};
+use crate::decimal::{Decimal, MAX_PRECISION};
+use crate::encoding::decode_binary;
+use crate::error::{Error, ErrorKind};
+use crate::pattern::Pattern;
 use crate::value::Value;
+use std::fmt;
 
 /// Type hint for GBLN values
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,41 +21,180 @@ pub enum TypeHint {
     I16,
     I32,
     I64,
+    I128,
 
     // Unsigned integers
     U8,
     U16,
     U32,
     U64,
+    U128,
 
     // Floats
     F32,
     F64,
 
+    // Exact decimal with precision and scale (`dec:10.2`)
+    Decimal(u32, u32),
+
     // String with max character count
     Str(usize),
 
+    // String hint with an extra constraint (`s32:ascii`, `s64:/[a-z]+/`)
+    ConstrainedStr(usize, StrConstraint),
+
+    // Temporal
+    Date,
+    Time,
+    DateTime,
+    Timestamp,
+
+    // Binary with optional max byte count
+    Bin(Option<usize>),
+
     // Boolean
     Bool,
 
     // Null
     Null,
+
+    // Any other hint that also accepts null (`u32?`)
+    Nullable(Box<TypeHint>),
+
+    // Numeric hint restricted to a range (`u8:1..64`, `f32:-40..85`)
+    Ranged(Box<TypeHint>, NumRange),
+
+    // String from a closed set of variants (`e:low|medium|high`)
+    Enum(Vec<String>),
+}
+
+/// Inclusive bounds of a range-constrained numeric hint
+///
+/// Written `min..max` after the type (`u16:1..65535`); either bound may be
+/// left out (`i32:..0`, `u8:1..`). Integer hints take integer bounds within
+/// the width of the type, float hints take finite float bounds. `f32` values
+/// are compared with the bounds rounded to `f32`. `u128` takes no range, as
+/// bounds are stored as `i128`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumRange {
+    Integer {
+        min: Option<i128>,
+        max: Option<i128>,
+    },
+    Float {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+// Float bounds are finite (`TypeHint::from_str` rejects NaN), so equality is
+// reflexive
+impl Eq for NumRange {}
+
+/// Inclusive bounds on the number of elements in an array
+///
+/// Written after the hint with `;`: `u8;..16` (at most 16), `f32;3`
+/// (exactly 3), `s8;1..` (at least one), `;..16` for untyped arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LenRange {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+/// Extra constraint on a string hint, written after the length
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrConstraint {
+    /// `s32:ascii`: ASCII characters only
+    Ascii,
+
+    /// `s32:bytes`: the length bounds UTF-8 bytes instead of characters
+    Bytes,
+
+    /// `s64:/regex/`: the whole string matches the pattern
+    Pattern(Pattern),
+}
+
+/// How an object field was declared in the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Declaration {
+    /// `key(value)` or `key[...]`: no type hint, type inferred
+    Untyped,
+
+    /// `key<hint>(value)`
+    Typed(TypeHint),
+
+    /// `key<hint>[...]`: typed array
+    TypedArray(TypeHint),
+
+    /// `key<{col<hint> ...}>[(...) ...]`: array of records sharing one
+    /// schema, with column names and hints in declaration order
+    Table(Vec<(String, TypeHint)>),
+
+    /// `key<hint;len>[...]`: an array declaration with length bounds
+    Bounded(Box<Declaration>, LenRange),
 }
 
 impl TypeHint {
-    /// Parse type hint from string (e.g., "u32", "s64", "b")
+    /// Parse type hint from string (e.g., "u32", "s64", "b", "u32?")
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self, String> {
+        if let Some(inner) = s.strip_suffix('?') {
+            return match TypeHint::from_str(inner)? {
+                TypeHint::Null | TypeHint::Nullable(_) => {
+                    Err(format!("Type hint {} cannot be made nullable", inner))
+                }
+                hint => Ok(TypeHint::Nullable(Box::new(hint))),
+            };
+        }
+
+        if let Some(variants) = s.strip_prefix("e:") {
+            return parse_variants(variants).map(TypeHint::Enum);
+        }
+
+        if let Some(max_len) = s.strip_prefix("bin:") {
+            let max_len = max_len
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid binary type: {}", s))?;
+            return Ok(TypeHint::Bin(Some(max_len)));
+        }
+
+        if let Some(spec) = s.strip_prefix("dec:") {
+            return parse_decimal_spec(spec).ok_or_else(|| {
+                format!(
+                    "Invalid decimal type: {} (expected dec:P.S with 1 <= P <= {} and S <= P)",
+                    s, MAX_PRECISION
+                )
+            });
+        }
+
+        if let Some((base, refinement)) = s.split_once(':') {
+            let base = TypeHint::from_str(base)?;
+            if let TypeHint::Str(max_len) = base {
+                let constraint = StrConstraint::parse(refinement)?;
+                return Ok(TypeHint::ConstrainedStr(max_len, constraint));
+            }
+            let range = NumRange::parse(&base, refinement)?;
+            return Ok(TypeHint::Ranged(Box::new(base), range));
+        }
+
         match s {
             "i8" => Ok(TypeHint::I8),
             "i16" => Ok(TypeHint::I16),
             "i32" => Ok(TypeHint::I32),
             "i64" => Ok(TypeHint::I64),
+            "i128" => Ok(TypeHint::I128),
             "u8" => Ok(TypeHint::U8),
             "u16" => Ok(TypeHint::U16),
             "u32" => Ok(TypeHint::U32),
             "u64" => Ok(TypeHint::U64),
+            "u128" => Ok(TypeHint::U128),
             "f32" => Ok(TypeHint::F32),
             "f64" => Ok(TypeHint::F64),
+            "date" => Ok(TypeHint::Date),
+            "time" => Ok(TypeHint::Time),
+            "dt" => Ok(TypeHint::DateTime),
+            "ts" => Ok(TypeHint::Timestamp),
+            "bin" => Ok(TypeHint::Bin(None)),
             "b" => Ok(TypeHint::Bool),
             "n" => Ok(TypeHint::Null),
             _ if s.starts_with('s') => {
@@ -70,109 +215,676 @@ impl TypeHint {
             TypeHint::I16 => "i16".to_string(),
             TypeHint::I32 => "i32".to_string(),
             TypeHint::I64 => "i64".to_string(),
+            TypeHint::I128 => "i128".to_string(),
             TypeHint::U8 => "u8".to_string(),
             TypeHint::U16 => "u16".to_string(),
             TypeHint::U32 => "u32".to_string(),
             TypeHint::U64 => "u64".to_string(),
+            TypeHint::U128 => "u128".to_string(),
             TypeHint::F32 => "f32".to_string(),
             TypeHint::F64 => "f64".to_string(),
+            TypeHint::Decimal(precision, scale) => format!("dec:{}.{}", precision, scale),
             TypeHint::Str(n) => format!("s{}", n),
+            TypeHint::ConstrainedStr(n, constraint) => format!("s{}:{}", n, constraint),
+            TypeHint::Date => "date".to_string(),
+            TypeHint::Time => "time".to_string(),
+            TypeHint::DateTime => "dt".to_string(),
+            TypeHint::Timestamp => "ts".to_string(),
+            TypeHint::Bin(None) => "bin".to_string(),
+            TypeHint::Bin(Some(n)) => format!("bin:{}", n),
             TypeHint::Bool => "b".to_string(),
             TypeHint::Null => "n".to_string(),
+            TypeHint::Nullable(inner) => format!("{}?", inner.as_str()),
+            TypeHint::Ranged(base, range) => format!("{}:{}", base.as_str(), range),
+            TypeHint::Enum(variants) => format!("e:{}", variants.join("|")),
         }
     }
 
-    /// Parse value from string according to type hint with validation
-    pub fn parse_value(&self, s: &str) -> Result<Value, String> {
+    /// Check if the hint accepts null values (`n` or a `?` hint)
+    pub fn is_nullable(&self) -> bool {
+        matches!(self, TypeHint::Null | TypeHint::Nullable(_))
+    }
+
+    /// The hint validating non-null values (strips a `?` modifier)
+    pub fn non_null(&self) -> &TypeHint {
         match self {
-            TypeHint::I8 => {
-                let val = s
-                    .parse::<i8>()
-                    .map_err(|_| format!("Cannot parse '{}' as i8", s))?;
-                Ok(Value::I8(val))
-            }
-            TypeHint::I16 => {
-                let val = s
-                    .parse::<i16>()
-                    .map_err(|_| format!("Cannot parse '{}' as i16", s))?;
-                Ok(Value::I16(val))
-            }
-            TypeHint::I32 => {
-                let val = s
-                    .parse::<i32>()
-                    .map_err(|_| format!("Cannot parse '{}' as i32", s))?;
-                Ok(Value::I32(val))
+            TypeHint::Nullable(inner) => inner,
+            hint => hint,
+        }
+    }
+
+    /// Inclusive value range of an integer type hint
+    ///
+    /// `u128` has no range here, as its upper half does not fit an `i128`.
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        match self {
+            TypeHint::I8 => Some((i8::MIN.into(), i8::MAX.into())),
+            TypeHint::I16 => Some((i16::MIN.into(), i16::MAX.into())),
+            TypeHint::I32 => Some((i32::MIN.into(), i32::MAX.into())),
+            TypeHint::I64 => Some((i64::MIN.into(), i64::MAX.into())),
+            TypeHint::I128 => Some((i128::MIN, i128::MAX)),
+            TypeHint::U8 => Some((0, u8::MAX.into())),
+            TypeHint::U16 => Some((0, u16::MAX.into())),
+            TypeHint::U32 => Some((0, u32::MAX.into())),
+            TypeHint::U64 => Some((0, u64::MAX.into())),
+            _ => None,
+        }
+    }
+
+    /// Check that an integer fits the range of this type hint
+    pub fn check_integer(&self, n: i128) -> Result<(), Error> {
+        let (min, max) = self.integer_range().ok_or_else(|| {
+            Error::value(
+                ErrorKind::TypeMismatch,
+                format!("{} is not an integer type", self.as_str()),
+            )
+        })?;
+        if n < min || n > max {
+            return Err(Error::value(
+                ErrorKind::IntegerOutOfRange,
+                format!(
+                    "Integer {} out of range for {} ({} to {})",
+                    n,
+                    self.as_str(),
+                    min,
+                    max
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Build an integer value of this type hint, checking its range
+    pub fn integer_value(&self, n: i128) -> Result<Value, Error> {
+        self.check_integer(n)?;
+        Ok(match self {
+            TypeHint::I8 => Value::I8(n as i8),
+            TypeHint::I16 => Value::I16(n as i16),
+            TypeHint::I32 => Value::I32(n as i32),
+            TypeHint::I64 => Value::I64(n as i64),
+            TypeHint::I128 => Value::I128(n),
+            TypeHint::U8 => Value::U8(n as u8),
+            TypeHint::U16 => Value::U16(n as u16),
+            TypeHint::U32 => Value::U32(n as u32),
+            TypeHint::U64 => Value::U64(n as u64),
+            _ => unreachable!("check_integer rejects non-integer hints"),
+        })
+    }
+
+    /// Check that a parsed number is written in a notation the dialect
+    /// accepts
+    fn check_notation(&self, s: &str, dialect: Dialect) -> Result<(), Error> {
+        if dialect.allows_number(s) {
+            return Ok(());
+        }
+        Err(mismatch(format!(
+            "Cannot parse '{}' as {} (strict dialect expects plain decimal notation)",
+            s,
+            self.as_str()
+        )))
+    }
+
+    /// Check the notation of a parsed float and whether it may be
+    /// non-finite
+    fn check_float(&self, s: &str, finite: bool, options: &ParseOptions) -> Result<(), Error> {
+        if !options.dialect.allows_float(s) {
+            return Err(mismatch(format!(
+                "Cannot parse '{}' as {} (strict dialect expects decimal or exponent notation, nan, inf or -inf)",
+                s,
+                self.as_str()
+            )));
+        }
+        if !finite && !options.allow_non_finite {
+            return Err(Error::value(
+                ErrorKind::NonFiniteFloat,
+                format!("Non-finite float '{}' is not allowed", s),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Error for integer content that does not parse as this hint: out of
+    /// range if it is an integer literal too large for any `i128`/`u128`,
+    /// a type mismatch otherwise
+    fn integer_error(&self, s: &str) -> Error {
+        let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return mismatch(format!("Cannot parse '{}' as {}", s, self.as_str()));
+        }
+        let range = match self.integer_range() {
+            Some((min, max)) => format!("{} to {}", min, max),
+            None => format!("0 to {}", u128::MAX),
+        };
+        Error::value(
+            ErrorKind::IntegerOutOfRange,
+            format!(
+                "Integer {} out of range for {} ({})",
+                s,
+                self.as_str(),
+                range
+            ),
+        )
+    }
+
+    /// Check that a string satisfies this string type hint
+    ///
+    /// `sN` hints check the character count (or byte count for
+    /// `sN:bytes`) and any constraint, enum hints check that the string is
+    /// one of the variants.
+    pub fn check_str(&self, s: &str) -> Result<(), Error> {
+        match self {
+            TypeHint::Str(max_len) => check_char_count(s, *max_len),
+            TypeHint::ConstrainedStr(max_len, StrConstraint::Bytes) => {
+                if s.len() > *max_len {
+                    return Err(Error::value(
+                        ErrorKind::BytesTooLong,
+                        format!("String too long: {} bytes (max {})", s.len(), max_len),
+                    ));
+                }
+                Ok(())
             }
-            TypeHint::I64 => {
-                let val = s
-                    .parse::<i64>()
-                    .map_err(|_| format!("Cannot parse '{}' as i64", s))?;
-                Ok(Value::I64(val))
+            TypeHint::ConstrainedStr(max_len, StrConstraint::Ascii) => {
+                check_char_count(s, *max_len)?;
+                match s.chars().find(|c| !c.is_ascii()) {
+                    Some(c) => Err(mismatch(format!(
+                        "String '{}' is not ASCII (contains '{}')",
+                        s, c
+                    ))),
+                    None => Ok(()),
+                }
             }
-            TypeHint::U8 => {
-                let val = s
-                    .parse::<u8>()
-                    .map_err(|_| format!("Cannot parse '{}' as u8", s))?;
-                Ok(Value::U8(val))
+            TypeHint::ConstrainedStr(max_len, StrConstraint::Pattern(pattern)) => {
+                check_char_count(s, *max_len)?;
+                if !pattern.is_match(s) {
+                    return Err(Error::value(
+                        ErrorKind::PatternMismatch,
+                        format!("String '{}' does not match {}", s, pattern),
+                    ));
+                }
+                Ok(())
             }
-            TypeHint::U16 => {
-                let val = s
-                    .parse::<u16>()
-                    .map_err(|_| format!("Cannot parse '{}' as u16", s))?;
-                Ok(Value::U16(val))
+            TypeHint::Enum(variants) => {
+                if variants.iter().any(|v| v == s) {
+                    return Ok(());
+                }
+                let mut error = Error::value(
+                    ErrorKind::UnknownVariant,
+                    format!("'{}' is not one of {}", s, variants.join("|")),
+                );
+                if let Some(closest) = closest_variant(variants, s) {
+                    error = error.with_suggestion(format!("did you mean '{}'?", closest));
+                }
+                Err(error)
             }
-            TypeHint::U32 => {
-                let val = s
-                    .parse::<u32>()
-                    .map_err(|_| format!("Cannot parse '{}' as u32", s))?;
-                Ok(Value::U32(val))
+            _ => Err(Error::value(
+                ErrorKind::TypeMismatch,
+                format!("{} is not a string type", self.as_str()),
+            )),
+        }
+    }
+
+    /// Parse value from string according to type hint with validation
+    ///
+    /// Errors carry no position; the parser places them in the input.
+    pub fn parse_value(&self, s: &str) -> Result<Value, Error> {
+        self.parse_value_with_options(s, &ParseOptions::default())
+    }
+
+    /// Parse value from string, accepting the literal spellings of the
+    /// dialect in `options`
+    pub fn parse_value_with_options(
+        &self,
+        s: &str,
+        options: &ParseOptions,
+    ) -> Result<Value, Error> {
+        let dialect = options.dialect;
+        match self {
+            TypeHint::I8
+            | TypeHint::I16
+            | TypeHint::I32
+            | TypeHint::I64
+            | TypeHint::I128
+            | TypeHint::U8
+            | TypeHint::U16
+            | TypeHint::U32
+            | TypeHint::U64 => {
+                let n = s.parse::<i128>().map_err(|_| self.integer_error(s))?;
+                self.check_notation(s, dialect)?;
+                self.integer_value(n)
             }
-            TypeHint::U64 => {
-                let val = s
-                    .parse::<u64>()
-                    .map_err(|_| format!("Cannot parse '{}' as u64", s))?;
-                Ok(Value::U64(val))
+            TypeHint::U128 => {
+                let n = s.parse::<u128>().map_err(|_| self.integer_error(s))?;
+                self.check_notation(s, dialect)?;
+                Ok(Value::U128(n))
             }
             TypeHint::F32 => {
                 let val = s
                     .parse::<f32>()
-                    .map_err(|_| format!("Cannot parse '{}' as f32", s))?;
+                    .map_err(|_| mismatch(format!("Cannot parse '{}' as f32", s)))?;
+                self.check_float(s, val.is_finite(), options)?;
                 Ok(Value::F32(val))
             }
             TypeHint::F64 => {
                 let val = s
                     .parse::<f64>()
-                    .map_err(|_| format!("Cannot parse '{}' as f64", s))?;
+                    .map_err(|_| mismatch(format!("Cannot parse '{}' as f64", s)))?;
+                self.check_float(s, val.is_finite(), options)?;
                 Ok(Value::F64(val))
             }
-            TypeHint::Str(max_len) => {
-                let char_count = s.chars().count();
-                if char_count > *max_len {
-                    return Err(format!(
-                        "String too long: {} characters (max {})",
-                        char_count, max_len
-                    ));
+            TypeHint::Decimal(precision, scale) => {
+                let decimal = s.parse::<Decimal>()?;
+                if decimal.scale() > *scale && decimal.with_scale(*scale).is_none() {
+                    return Err(mismatch(format!(
+                        "Decimal {} has more than {} fractional digits for {}",
+                        s,
+                        scale,
+                        self.as_str()
+                    )));
                 }
+                match decimal.with_scale(*scale) {
+                    Some(d) if d.digits() <= *precision => Ok(Value::Decimal(d)),
+                    _ => Err(Error::value(
+                        ErrorKind::ValueOutOfRange,
+                        format!(
+                            "Decimal {} out of range for {} (at most {} integer digits)",
+                            s,
+                            self.as_str(),
+                            precision - scale
+                        ),
+                    )),
+                }
+            }
+            TypeHint::Str(_) | TypeHint::ConstrainedStr(..) | TypeHint::Enum(_) => {
+                self.check_str(s)?;
                 Ok(Value::Str(s.to_string()))
             }
-            TypeHint::Bool => match s {
-                "t" | "true" => Ok(Value::Bool(true)),
-                "f" | "false" => Ok(Value::Bool(false)),
-                _ => Err(format!(
-                    "Invalid boolean value: '{}' (expected t/f or true/false)",
-                    s
-                )),
+            TypeHint::Date => s.parse().map(Value::Date),
+            TypeHint::Time => s.parse().map(Value::Time),
+            TypeHint::DateTime => s.parse().map(Value::DateTime),
+            TypeHint::Timestamp => s.parse().map(Value::Timestamp),
+            TypeHint::Bin(max_len) => {
+                let bytes = decode_binary(s).map_err(mismatch)?;
+                match max_len {
+                    Some(max_len) if bytes.len() > *max_len => Err(Error::value(
+                        ErrorKind::BytesTooLong,
+                        format!("Binary too long: {} bytes (max {})", bytes.len(), max_len),
+                    )),
+                    _ => Ok(Value::Bytes(bytes)),
+                }
+            }
+            TypeHint::Bool => match dialect.parse_bool(s) {
+                Some(b) => Ok(Value::Bool(b)),
+                None => Err(mismatch(format!(
+                    "Invalid boolean value: '{}' (expected {})",
+                    s,
+                    match dialect {
+                        Dialect::Strict => "t/f or true/false",
+                        Dialect::Lenient => "t/f, true/false, 1/0 or yes/no",
+                    }
+                ))),
             },
             TypeHint::Null => {
-                if s.is_empty() || s == "null" {
+                if dialect.is_null(s) {
+                    Ok(Value::Null)
+                } else {
+                    Err(mismatch(format!(
+                        "Invalid null value: '{}' (expected {})",
+                        s,
+                        match dialect {
+                            Dialect::Strict => "empty or 'null'",
+                            Dialect::Lenient => "empty, 'null' or 'n'",
+                        }
+                    )))
+                }
+            }
+            TypeHint::Nullable(inner) => {
+                if dialect.is_null(s) {
                     Ok(Value::Null)
                 } else {
-                    Err(format!(
-                        "Invalid null value: '{}' (expected empty or 'null')",
-                        s
-                    ))
+                    inner.parse_value_with_options(s, options)
                 }
             }
+            TypeHint::Ranged(base, range) => {
+                let value = base.parse_value_with_options(s, options)?;
+                if !range.contains(&value) {
+                    return Err(Error::value(
+                        ErrorKind::ValueOutOfRange,
+                        format!(
+                            "Value {} out of range for {} ({})",
+                            s,
+                            self.as_str(),
+                            range.describe()
+                        ),
+                    ));
+                }
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl StrConstraint {
+    /// Parse the constraint after `sN:`
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "ascii" => Ok(StrConstraint::Ascii),
+            "bytes" => Ok(StrConstraint::Bytes),
+            _ => {
+                let source = s
+                    .strip_prefix('/')
+                    .and_then(|rest| rest.strip_suffix('/'))
+                    .ok_or_else(|| {
+                        format!(
+                            "Unknown string constraint '{}' (expected ascii, bytes or /regex/)",
+                            s
+                        )
+                    })?;
+                source
+                    .parse()
+                    .map(StrConstraint::Pattern)
+                    .map_err(|e: Error| e.message)
+            }
+        }
+    }
+}
+
+impl fmt::Display for StrConstraint {
+    /// Formats the constraint as written in a hint
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrConstraint::Ascii => write!(f, "ascii"),
+            StrConstraint::Bytes => write!(f, "bytes"),
+            StrConstraint::Pattern(pattern) => write!(f, "{}", pattern),
+        }
+    }
+}
+
+impl NumRange {
+    /// Parse `min..max` bounds for a numeric base hint
+    fn parse(base: &TypeHint, s: &str) -> Result<Self, String> {
+        let (min, max) = s
+            .split_once("..")
+            .ok_or_else(|| format!("Invalid range '{}' (expected min..max)", s))?;
+        if min.is_empty() && max.is_empty() {
+            return Err(format!("Range '{}' needs at least one bound", s));
+        }
+
+        let range = match base {
+            TypeHint::F32 | TypeHint::F64 => {
+                let bound = |t: &str| -> Result<Option<f64>, String> {
+                    if t.is_empty() {
+                        return Ok(None);
+                    }
+                    match t.parse::<f64>() {
+                        Ok(n) if n.is_finite() => Ok(Some(n)),
+                        _ => Err(format!("Invalid range bound '{}' for {}", t, base.as_str())),
+                    }
+                };
+                NumRange::Float {
+                    min: bound(min)?,
+                    max: bound(max)?,
+                }
+            }
+            _ if base.integer_range().is_some() => {
+                let bound = |t: &str| -> Result<Option<i128>, String> {
+                    if t.is_empty() {
+                        return Ok(None);
+                    }
+                    let n = t.parse::<i128>().map_err(|_| {
+                        format!("Invalid range bound '{}' for {}", t, base.as_str())
+                    })?;
+                    base.check_integer(n).map_err(|e| e.message)?;
+                    Ok(Some(n))
+                };
+                NumRange::Integer {
+                    min: bound(min)?,
+                    max: bound(max)?,
+                }
+            }
+            TypeHint::U128 => {
+                return Err(
+                    "Type hint u128 does not take a range (use u64 or i128 instead)".to_string(),
+                )
+            }
+            _ => return Err(format!("Type hint {} does not take a range", base.as_str())),
+        };
+
+        if !range.is_ordered() {
+            return Err(format!("Range '{}' has min greater than max", s));
+        }
+        Ok(range)
+    }
+
+    /// Check if a numeric value lies within the bounds
+    pub fn contains(&self, value: &Value) -> bool {
+        match self {
+            NumRange::Integer { min, max } => value
+                .as_integer()
+                .is_some_and(|n| min.is_none_or(|min| n >= min) && max.is_none_or(|max| n <= max)),
+            // Compare at the width of the value, so that `0.1` is within
+            // `f32:0..0.1`
+            NumRange::Float { min, max } => match *value {
+                Value::F32(n) => {
+                    min.is_none_or(|min| n >= min as f32) && max.is_none_or(|max| n <= max as f32)
+                }
+                _ => value.as_f64().is_some_and(|n| {
+                    min.is_none_or(|min| n >= min) && max.is_none_or(|max| n <= max)
+                }),
+            },
+        }
+    }
+
+    /// Human-readable bounds for error messages (e.g. "1 to 64")
+    fn describe(&self) -> String {
+        let (min, max) = match self {
+            NumRange::Integer { min, max } => {
+                (min.map(|n| n.to_string()), max.map(|n| n.to_string()))
+            }
+            NumRange::Float { min, max } => {
+                (min.map(|n| n.to_string()), max.map(|n| n.to_string()))
+            }
+        };
+        match (min, max) {
+            (Some(min), Some(max)) => format!("{} to {}", min, max),
+            (Some(min), None) => format!("at least {}", min),
+            (None, Some(max)) => format!("at most {}", max),
+            (None, None) => "any value".to_string(),
+        }
+    }
+
+    fn is_ordered(&self) -> bool {
+        match self {
+            NumRange::Integer {
+                min: Some(min),
+                max: Some(max),
+            } => min <= max,
+            NumRange::Float {
+                min: Some(min),
+                max: Some(max),
+            } => min <= max,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for NumRange {
+    /// Formats the bounds as written in a hint (`1..64`, `-40..`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn bound<T: fmt::Display>(n: &Option<T>) -> String {
+            n.as_ref().map(|n| n.to_string()).unwrap_or_default()
+        }
+        match self {
+            NumRange::Integer { min, max } => write!(f, "{}..{}", bound(min), bound(max)),
+            NumRange::Float { min, max } => write!(f, "{}..{}", bound(min), bound(max)),
         }
     }
 }
+
+impl LenRange {
+    /// Exactly `n` elements
+    pub fn exact(n: usize) -> Self {
+        LenRange {
+            min: n,
+            max: Some(n),
+        }
+    }
+
+    /// Parse `n`, `min..max`, `..max` or `min..`
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid array length '{}'", s);
+        let bound = |t: &str| -> Result<Option<usize>, String> {
+            if t.is_empty() {
+                return Ok(None);
+            }
+            t.parse::<usize>().map(Some).map_err(|_| invalid())
+        };
+
+        let Some((min, max)) = s.split_once("..") else {
+            return bound(s)?.map(LenRange::exact).ok_or_else(invalid);
+        };
+        if min.is_empty() && max.is_empty() {
+            return Err(format!("Array length '{}' needs at least one bound", s));
+        }
+
+        let range = LenRange {
+            min: bound(min)?.unwrap_or(0),
+            max: bound(max)?,
+        };
+        if range.max.is_some_and(|max| max < range.min) {
+            return Err(format!("Empty array length range '{}'", s));
+        }
+        Ok(range)
+    }
+
+    /// Check if an array of `len` elements is within the bounds
+    pub fn contains(&self, len: usize) -> bool {
+        len >= self.min && self.max.is_none_or(|max| len <= max)
+    }
+
+    /// Fail if an array already holding `len` elements cannot take another
+    ///
+    /// Lets the parser stop at the first element over the limit instead of
+    /// reading the whole array.
+    pub(crate) fn check_push(&self, len: usize) -> Result<(), Error> {
+        match self.max {
+            Some(max) if len >= max => Err(Error::value(
+                ErrorKind::TooManyItems,
+                format!(
+                    "Array has more than {} items (expected {})",
+                    max,
+                    self.describe()
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if a complete array of `len` elements is outside the bounds
+    pub(crate) fn check_len(&self, len: usize) -> Result<(), Error> {
+        if len < self.min {
+            return Err(Error::value(
+                ErrorKind::TooFewItems,
+                format!("Array has {} items (expected {})", len, self.describe()),
+            ));
+        }
+        if self.max.is_some_and(|max| len > max) {
+            return Err(Error::value(
+                ErrorKind::TooManyItems,
+                format!("Array has {} items (expected {})", len, self.describe()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Bounds in words for error messages
+    fn describe(&self) -> String {
+        match (self.min, self.max) {
+            (min, Some(max)) if min == max => format!("exactly {}", max),
+            (0, Some(max)) => format!("at most {}", max),
+            (min, None) => format!("at least {}", min),
+            (min, Some(max)) => format!("{} to {}", min, max),
+        }
+    }
+}
+
+impl fmt::Display for LenRange {
+    /// Formats the bounds as written after `;` (`3`, `..16`, `1..`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (min, Some(max)) if min == max => write!(f, "{}", max),
+            (0, Some(max)) => write!(f, "..{}", max),
+            (min, None) => write!(f, "{}..", min),
+            (min, Some(max)) => write!(f, "{}..{}", min, max),
+        }
+    }
+}
+
+/// Parse the `P.S` precision and scale of a decimal hint
+fn parse_decimal_spec(spec: &str) -> Option<TypeHint> {
+    let (precision, scale) = spec.split_once('.')?;
+    let precision = precision.parse::<u32>().ok()?;
+    let scale = scale.parse::<u32>().ok()?;
+    ((1..=MAX_PRECISION).contains(&precision) && scale <= precision)
+        .then_some(TypeHint::Decimal(precision, scale))
+}
+
+/// Parse the `|`-separated variants of an enum hint
+fn parse_variants(s: &str) -> Result<Vec<String>, String> {
+    let mut variants: Vec<String> = Vec::new();
+    for variant in s.split('|') {
+        if variant.is_empty() || variant.chars().any(char::is_whitespace) {
+            return Err(format!("Invalid enum variant '{}' in e:{}", variant, s));
+        }
+        if variants.iter().any(|v| v == variant) {
+            return Err(format!("Duplicate enum variant '{}' in e:{}", variant, s));
+        }
+        variants.push(variant.to_string());
+    }
+    Ok(variants)
+}
+
+/// The variant with the smallest edit distance to `s`
+fn closest_variant<'v>(variants: &'v [String], s: &str) -> Option<&'v str> {
+    variants
+        .iter()
+        .min_by_key(|v| edit_distance(v, s))
+        .map(String::as_str)
+}
+
+/// Levenshtein distance between two strings, by character
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Check that a string has at most `max_len` characters
+fn check_char_count(s: &str, max_len: usize) -> Result<(), Error> {
+    let char_count = s.chars().count();
+    if char_count > max_len {
+        return Err(Error::value(
+            ErrorKind::StringTooLong,
+            format!(
+                "String too long: {} characters (max {})",
+                char_count, max_len
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Error for content that cannot be read as the hinted type
+fn mismatch(message: String) -> Error {
+    Error::value(ErrorKind::TypeMismatch, message)
+}
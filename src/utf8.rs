@@ -0,0 +1,148 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental UTF-8 decoding for streamed input
+//!
+//! The lexer reads a [`BufRead`] through a window of decoded text. Text
+//! before the token being read is released, so the window stays around the
+//! size of the reader's buffer rather than the whole document.
+
+use crate::error::Error;
+use std::io::{BufRead, ErrorKind as IoErrorKind};
+
+/// Release consumed text only once at least this many bytes have piled up
+const MIN_RELEASE: usize = 1024;
+
+/// Decoded text window over a byte reader
+pub(crate) struct Utf8Reader<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    /// Decoded text, starting at byte `offset` of the document
+    text: String,
+    offset: usize,
+    /// Start of a character split across reads (at most 3 bytes)
+    partial: Vec<u8>,
+    /// Read or decoding error, reported by the lexer in place of the rest
+    /// of the input
+    error: Option<Error>,
+    done: bool,
+}
+
+impl<'a> Utf8Reader<'a> {
+    pub(crate) fn new<R: BufRead + 'a>(reader: R) -> Self {
+        Self {
+            reader: Box::new(reader),
+            text: String::new(),
+            offset: 0,
+            partial: Vec::new(),
+            error: None,
+            done: false,
+        }
+    }
+
+    /// Character starting at byte `pos` of the document, reading more input
+    /// as needed; `None` at the end of the input or after an error
+    pub(crate) fn char_at(&mut self, pos: usize) -> Option<char> {
+        while pos - self.offset >= self.text.len() {
+            if !self.fill() {
+                return None;
+            }
+        }
+        self.text[pos - self.offset..].chars().next()
+    }
+
+    /// Text between two byte offsets of the document, both within the window
+    pub(crate) fn slice(&self, start: usize, end: usize) -> &str {
+        &self.text[start - self.offset..end - self.offset]
+    }
+
+    /// Allow text before byte `pos` to be dropped
+    pub(crate) fn release(&mut self, pos: usize) {
+        let consumed = pos - self.offset;
+        // Draining only once half the window is consumed keeps the copying
+        // linear in the input size
+        if consumed >= MIN_RELEASE && consumed * 2 >= self.text.len() {
+            self.text.drain(..consumed);
+            self.offset = pos;
+        }
+    }
+
+    /// Take the error that ended the input early, if any
+    pub(crate) fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    /// Decode the next chunk of input into the window
+    fn fill(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+
+        let chunk = loop {
+            match self.reader.fill_buf() {
+                Ok(chunk) => break chunk,
+                Err(e) if e.kind() == IoErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.error = Some(Error::io(format!("Failed to read input: {}", e)));
+                    self.done = true;
+                    return false;
+                }
+            }
+        };
+
+        if chunk.is_empty() {
+            if !self.partial.is_empty() {
+                self.error = Some(invalid_utf8(self.offset + self.text.len()));
+            }
+            self.done = true;
+            return false;
+        }
+
+        let len = chunk.len();
+        let decoded = decode_chunk(chunk, &mut self.partial, &mut self.text);
+        self.reader.consume(len);
+
+        if !decoded {
+            self.error = Some(invalid_utf8(self.offset + self.text.len()));
+            self.done = true;
+            return false;
+        }
+        true
+    }
+}
+
+/// Append the complete characters of `chunk` to `text`, keeping an
+/// incomplete character at the end in `partial`; false on invalid UTF-8
+fn decode_chunk(chunk: &[u8], partial: &mut Vec<u8>, text: &mut String) -> bool {
+    let mut rest = chunk;
+
+    // Finish a character split across reads
+    while !partial.is_empty() && !rest.is_empty() {
+        partial.push(rest[0]);
+        rest = &rest[1..];
+        match std::str::from_utf8(partial) {
+            Ok(s) => {
+                text.push_str(s);
+                partial.clear();
+            }
+            Err(e) if e.error_len().is_some() => return false,
+            Err(_) => {}
+        }
+    }
+
+    match std::str::from_utf8(rest) {
+        Ok(s) => text.push_str(s),
+        Err(e) => {
+            let (valid, tail) = rest.split_at(e.valid_up_to());
+            text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+            if e.error_len().is_some() {
+                return false;
+            }
+            partial.extend_from_slice(tail);
+        }
+    }
+    true
+}
+
+fn invalid_utf8(offset: usize) -> Error {
+    Error::io(format!("Input is not valid UTF-8 (at byte {})", offset))
+}
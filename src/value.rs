@@ -5,30 +5,54 @@
 //!
 //! The in-memory representation of parsed GBLN data.
 
-use std::collections::HashMap;
+use crate::decimal::Decimal;
+use crate::map::Map;
+use crate::temporal::{Date, DateTime, Time, Timestamp};
 
 /// A GBLN value
-#[derive(Debug, Clone, PartialEq)]
+///
+/// `Value` implements `Eq`, `Hash` and `Ord`. Comparison is structural: the
+/// variant matters (`U8(5) != I64(5)`; see [`Value::semantic_eq`] for
+/// comparing numbers by value). Floats are canonicalised first: every NaN is
+/// the same value and `-0.0` equals `0.0`; after that floats compare by
+/// `total_cmp`, so NaN sorts above positive infinity. Objects compare their
+/// fields regardless of insertion order. Values of different variants are
+/// ordered by variant declaration order.
+#[derive(Debug, Clone)]
 pub enum Value {
     // Signed integers
     I8(i8),
     I16(i16),
     I32(i32),
     I64(i64),
+    I128(i128),
 
     // Unsigned integers
     U8(u8),
     U16(u16),
     U32(u32),
     U64(u64),
+    U128(u128),
 
     // Floats
     F32(f32),
     F64(f64),
 
+    // Exact decimal
+    Decimal(Decimal),
+
     // String
     Str(String),
 
+    // Temporal
+    Date(Date),
+    Time(Time),
+    DateTime(DateTime),
+    Timestamp(Timestamp),
+
+    // Binary
+    Bytes(Vec<u8>),
+
     // Boolean
     Bool(bool),
 
@@ -36,31 +60,99 @@ pub enum Value {
     Null,
 
     // Object (preserves insertion order)
-    Object(HashMap<String, Value>),
+    Object(Map),
 
     // Array
     Array(Vec<Value>),
 }
 
 impl Value {
-    // Type checking methods
+    // Type checking methods (exact variant)
 
     pub fn is_i8(&self) -> bool {
         matches!(self, Value::I8(_))
     }
 
+    pub fn is_i16(&self) -> bool {
+        matches!(self, Value::I16(_))
+    }
+
+    pub fn is_i32(&self) -> bool {
+        matches!(self, Value::I32(_))
+    }
+
+    pub fn is_i64(&self) -> bool {
+        matches!(self, Value::I64(_))
+    }
+
+    pub fn is_i128(&self) -> bool {
+        matches!(self, Value::I128(_))
+    }
+
+    pub fn is_u8(&self) -> bool {
+        matches!(self, Value::U8(_))
+    }
+
+    pub fn is_u16(&self) -> bool {
+        matches!(self, Value::U16(_))
+    }
+
     pub fn is_u32(&self) -> bool {
         matches!(self, Value::U32(_))
     }
 
+    pub fn is_u64(&self) -> bool {
+        matches!(self, Value::U64(_))
+    }
+
+    pub fn is_u128(&self) -> bool {
+        matches!(self, Value::U128(_))
+    }
+
+    pub fn is_f32(&self) -> bool {
+        matches!(self, Value::F32(_))
+    }
+
+    pub fn is_f64(&self) -> bool {
+        matches!(self, Value::F64(_))
+    }
+
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, Value::Decimal(_))
+    }
+
     pub fn is_string(&self) -> bool {
         matches!(self, Value::Str(_))
     }
 
+    pub fn is_date(&self) -> bool {
+        matches!(self, Value::Date(_))
+    }
+
+    pub fn is_time(&self) -> bool {
+        matches!(self, Value::Time(_))
+    }
+
+    pub fn is_datetime(&self) -> bool {
+        matches!(self, Value::DateTime(_))
+    }
+
+    pub fn is_timestamp(&self) -> bool {
+        matches!(self, Value::Timestamp(_))
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        matches!(self, Value::Bytes(_))
+    }
+
     pub fn is_bool(&self) -> bool {
         matches!(self, Value::Bool(_))
     }
 
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
     pub fn is_object(&self) -> bool {
         matches!(self, Value::Object(_))
     }
@@ -69,24 +161,115 @@ impl Value {
         matches!(self, Value::Array(_))
     }
 
-    // Value accessors
+    // Type checking methods (category)
+
+    /// Check if the value is any signed or unsigned integer variant
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Value::I8(_)
+                | Value::I16(_)
+                | Value::I32(_)
+                | Value::I64(_)
+                | Value::I128(_)
+                | Value::U8(_)
+                | Value::U16(_)
+                | Value::U32(_)
+                | Value::U64(_)
+                | Value::U128(_)
+        )
+    }
+
+    /// Check if the value is any float variant
+    pub fn is_float(&self) -> bool {
+        matches!(self, Value::F32(_) | Value::F64(_))
+    }
+
+    /// Check if the value is any integer, float or decimal variant
+    pub fn is_number(&self) -> bool {
+        self.is_integer() || self.is_float() || self.is_decimal()
+    }
+
+    // Integer accessors
+    //
+    // Each accepts any integer variant whose value fits the requested width,
+    // so `as_u16()` reads a `U8(200)` and `as_u8()` reads an `I64(4)`.
+    // Values that would be truncated or change sign return `None`.
+
+    pub fn as_i8(&self) -> Option<i8> {
+        self.as_integer().and_then(|n| i8::try_from(n).ok())
+    }
+
+    pub fn as_i16(&self) -> Option<i16> {
+        self.as_integer().and_then(|n| i16::try_from(n).ok())
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_integer().and_then(|n| i32::try_from(n).ok())
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_integer().and_then(|n| i64::try_from(n).ok())
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        self.as_integer()
+    }
+
+    pub fn as_u8(&self) -> Option<u8> {
+        self.as_integer().and_then(|n| u8::try_from(n).ok())
+    }
+
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_integer().and_then(|n| u16::try_from(n).ok())
+    }
 
     pub fn as_u32(&self) -> Option<u32> {
-        if let Value::U32(n) = self {
+        self.as_integer().and_then(|n| u32::try_from(n).ok())
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_integer().and_then(|n| u64::try_from(n).ok())
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Value::U128(n) => Some(*n),
+            _ => self.as_integer().and_then(|n| u128::try_from(n).ok()),
+        }
+    }
+
+    // Float accessors
+
+    /// Get an `F32` value (an `F64` is not narrowed)
+    pub fn as_f32(&self) -> Option<f32> {
+        if let Value::F32(n) = self {
             Some(*n)
         } else {
             None
         }
     }
 
-    pub fn as_i32(&self) -> Option<i32> {
-        if let Value::I32(n) = self {
-            Some(*n)
+    /// Get any float variant widened to `f64`
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::F32(n) => Some(f64::from(*n)),
+            Value::F64(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get a `Decimal` value (integers and floats are not converted)
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        if let Value::Decimal(d) = self {
+            Some(*d)
         } else {
             None
         }
     }
 
+    // Other accessors
+
     pub fn as_str(&self) -> Option<&str> {
         if let Value::Str(s) = self {
             Some(s)
@@ -95,6 +278,46 @@ impl Value {
         }
     }
 
+    pub fn as_date(&self) -> Option<Date> {
+        if let Value::Date(d) = self {
+            Some(*d)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_time(&self) -> Option<Time> {
+        if let Value::Time(t) = self {
+            Some(*t)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_datetime(&self) -> Option<DateTime> {
+        if let Value::DateTime(dt) = self {
+            Some(*dt)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_timestamp(&self) -> Option<Timestamp> {
+        if let Value::Timestamp(ts) = self {
+            Some(*ts)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        if let Value::Bytes(b) = self {
+            Some(b)
+        } else {
+            None
+        }
+    }
+
     pub fn as_bool(&self) -> Option<bool> {
         if let Value::Bool(b) = self {
             Some(*b)
@@ -103,7 +326,15 @@ impl Value {
         }
     }
 
-    pub fn as_object(&self) -> Option<&HashMap<String, Value>> {
+    pub fn as_null(&self) -> Option<()> {
+        if let Value::Null = self {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
         if let Value::Object(obj) = self {
             Some(obj)
         } else {
@@ -118,16 +349,282 @@ impl Value {
             None
         }
     }
-}
 
-// Index access for objects (syntactic sugar)
-impl std::ops::Index<&str> for Value {
-    type Output = Value;
+    pub fn as_object_mut(&mut self) -> Option<&mut Map> {
+        if let Value::Object(obj) = self {
+            Some(obj)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        if let Value::Array(arr) = self {
+            Some(arr)
+        } else {
+            None
+        }
+    }
+
+    /// Name of the value's type as used in messages (e.g. "u8", "object")
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::I8(_) => "i8",
+            Value::I16(_) => "i16",
+            Value::I32(_) => "i32",
+            Value::I64(_) => "i64",
+            Value::I128(_) => "i128",
+            Value::U8(_) => "u8",
+            Value::U16(_) => "u16",
+            Value::U32(_) => "u32",
+            Value::U64(_) => "u64",
+            Value::U128(_) => "u128",
+            Value::F32(_) => "f32",
+            Value::F64(_) => "f64",
+            Value::Decimal(_) => "decimal",
+            Value::Str(_) => "string",
+            Value::Date(_) => "date",
+            Value::Time(_) => "time",
+            Value::DateTime(_) => "datetime",
+            Value::Timestamp(_) => "timestamp",
+            Value::Bytes(_) => "bytes",
+            Value::Bool(_) => "bool",
+            Value::Null => "null",
+            Value::Object(_) => "object",
+            Value::Array(_) => "array",
+        }
+    }
+
+    // Navigation and editing
+
+    /// Get an object field (by key) or array element (by position)
+    ///
+    /// Returns `None` if the key or position is missing or the value is not
+    /// an object/array.
+    pub fn get<I: ValueIndex>(&self, index: I) -> Option<&Value> {
+        index.index_into(self)
+    }
+
+    /// Mutable variant of [`Value::get`]
+    pub fn get_mut<I: ValueIndex>(&mut self, index: I) -> Option<&mut Value> {
+        index.index_into_mut(self)
+    }
 
-    fn index(&self, key: &str) -> &Self::Output {
+    /// Insert a field into an object, returning the previous value
+    ///
+    /// New keys are appended; existing keys keep their position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not an object.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<Value>) -> Option<Value> {
         match self {
-            Value::Object(map) => map.get(key).unwrap_or(&Value::Null),
-            _ => &Value::Null,
+            Value::Object(map) => map.insert(key.into(), value.into()),
+            other => panic!("cannot insert a field into {}", other.type_name()),
+        }
+    }
+
+    /// Remove an object field or array element, returning it
+    ///
+    /// Array elements after the removed one shift down. Returns `None` if
+    /// nothing was removed.
+    pub fn remove<I: ValueIndex>(&mut self, index: I) -> Option<Value> {
+        index.remove_from(self)
+    }
+
+    /// Append an element to an array
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not an array.
+    pub fn push(&mut self, value: impl Into<Value>) {
+        match self {
+            Value::Array(arr) => arr.push(value.into()),
+            other => panic!("cannot push onto {}", other.type_name()),
+        }
+    }
+
+    /// Get a field, creating intermediate objects as needed
+    ///
+    /// This is the opt-in auto-vivifying counterpart of `value[key]`: `Null`
+    /// becomes an empty object and a missing field is created as an empty
+    /// object, so nested paths can be built in one chain.
+    ///
+    /// ```
+    /// use gbln::{Map, Value};
+    ///
+    /// let mut config = Value::Object(Map::new());
+    /// config.vivify("app").vivify("server").insert("port", Value::U16(8080));
+    /// assert_eq!(config["app"]["server"]["port"], Value::U16(8080));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is neither an object nor `Null`.
+    pub fn vivify(&mut self, key: &str) -> &mut Value {
+        if self.is_null() {
+            *self = Value::Object(Map::new());
+        }
+        match self {
+            Value::Object(map) => map
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Map::new())),
+            other => panic!("cannot index {} with key {:?}", other.type_name(), key),
+        }
+    }
+
+    /// Any integer variant widened to `i128` (`None` for a `U128` above
+    /// `i128::MAX`)
+    pub(crate) fn as_integer(&self) -> Option<i128> {
+        match self {
+            Value::I8(n) => Some(i128::from(*n)),
+            Value::I16(n) => Some(i128::from(*n)),
+            Value::I32(n) => Some(i128::from(*n)),
+            Value::I64(n) => Some(i128::from(*n)),
+            Value::I128(n) => Some(*n),
+            Value::U8(n) => Some(i128::from(*n)),
+            Value::U16(n) => Some(i128::from(*n)),
+            Value::U32(n) => Some(i128::from(*n)),
+            Value::U64(n) => Some(i128::from(*n)),
+            Value::U128(n) => i128::try_from(*n).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A type that can index into a `Value`: `&str`/`String` for object fields,
+/// `usize` for array elements
+pub trait ValueIndex {
+    /// Look up the indexed value
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value>;
+
+    /// Look up the indexed value mutably
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value>;
+
+    /// Look up the indexed value mutably for `IndexMut`, inserting `Null` for
+    /// a missing object field; panics on anything else that cannot be indexed
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value;
+
+    /// Remove the indexed value
+    fn remove_from(&self, value: &mut Value) -> Option<Value>;
+}
+
+impl ValueIndex for usize {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Array(arr) => arr.get(*self),
+            _ => None,
         }
     }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        match value {
+            Value::Array(arr) => arr.get_mut(*self),
+            _ => None,
+        }
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        match value {
+            Value::Array(arr) => {
+                let len = arr.len();
+                arr.get_mut(*self).unwrap_or_else(|| {
+                    panic!("index {} out of bounds for array of length {}", self, len)
+                })
+            }
+            other => panic!("cannot index {} with position {}", other.type_name(), self),
+        }
+    }
+
+    fn remove_from(&self, value: &mut Value) -> Option<Value> {
+        match value {
+            Value::Array(arr) if *self < arr.len() => Some(arr.remove(*self)),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndex for str {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Object(map) => map.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        match value {
+            Value::Object(map) => map.get_mut(self),
+            _ => None,
+        }
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        match value {
+            Value::Object(map) => map.entry(self.to_string()).or_insert(Value::Null),
+            other => panic!("cannot index {} with key {:?}", other.type_name(), self),
+        }
+    }
+
+    fn remove_from(&self, value: &mut Value) -> Option<Value> {
+        match value {
+            Value::Object(map) => map.remove(self),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndex for String {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        self.as_str().index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        self.as_str().index_into_mut(value)
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        self.as_str().index_or_insert(value)
+    }
+
+    fn remove_from(&self, value: &mut Value) -> Option<Value> {
+        self.as_str().remove_from(value)
+    }
+}
+
+impl<T: ValueIndex + ?Sized> ValueIndex for &T {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        (**self).index_into_mut(value)
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        (**self).index_or_insert(value)
+    }
+
+    fn remove_from(&self, value: &mut Value) -> Option<Value> {
+        (**self).remove_from(value)
+    }
+}
+
+// Index access for objects and arrays (syntactic sugar)
+// A missing key or position yields `Null` rather than panicking.
+impl<I: ValueIndex> std::ops::Index<I> for Value {
+    type Output = Value;
+
+    fn index(&self, index: I) -> &Self::Output {
+        index.index_into(self).unwrap_or(&Value::Null)
+    }
+}
+
+// Mutable index access
+// A missing object key is inserted as `Null` so `value["key"] = ...` works;
+// indexing a non-object by key or an array out of bounds panics.
+impl<I: ValueIndex> std::ops::IndexMut<I> for Value {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        index.index_or_insert(self)
+    }
 }
@@ -0,0 +1,219 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! GBLN Tree Traversal
+//!
+//! `Visitor`/`VisitorMut` for callbacks over a value tree and `Value::walk`
+//! for iterating `(path, value)` pairs. Both traverse in document order
+//! (pre-order: a value before its children, fields in insertion order) and
+//! both can prune subtrees.
+
+use crate::path::{PathSegment, ValuePath};
+use crate::value::Value;
+
+/// What to do after entering a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visit {
+    /// Descend into the value's children
+    Continue,
+
+    /// Do not descend into this value's children
+    SkipChildren,
+
+    /// Stop the traversal
+    Stop,
+}
+
+/// Read-only callbacks over a value tree
+///
+/// # Examples
+///
+/// ```
+/// use gbln::visit::{Visit, Visitor};
+/// use gbln::{parse, Value, ValuePath};
+///
+/// struct CountStrings(usize);
+///
+/// impl Visitor for CountStrings {
+///     fn enter(&mut self, _path: &ValuePath, value: &Value) -> Visit {
+///         if value.is_string() {
+///             self.0 += 1;
+///         }
+///         Visit::Continue
+///     }
+/// }
+///
+/// let value = parse("user{name<s8>(Ann)tags[a b]}").unwrap();
+/// let mut counter = CountStrings(0);
+/// value.accept(&mut counter);
+/// assert_eq!(counter.0, 3);
+/// ```
+pub trait Visitor {
+    /// Called for each value before its children
+    fn enter(&mut self, path: &ValuePath, value: &Value) -> Visit;
+
+    /// Called for each entered value after its children
+    fn leave(&mut self, _path: &ValuePath, _value: &Value) {}
+}
+
+/// Mutating callbacks over a value tree
+///
+/// `enter` may replace the value; the traversal then descends into the
+/// replacement. `leave` runs after the children, for bottom-up rewrites.
+pub trait VisitorMut {
+    /// Called for each value before its children
+    fn enter(&mut self, path: &ValuePath, value: &mut Value) -> Visit;
+
+    /// Called for each entered value after its children
+    fn leave(&mut self, _path: &ValuePath, _value: &mut Value) {}
+}
+
+impl Value {
+    /// Traverse the tree with a visitor
+    pub fn accept<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        accept(self, visitor, &mut ValuePath::new());
+    }
+
+    /// Traverse the tree with a mutating visitor
+    pub fn accept_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        accept_mut(self, visitor, &mut ValuePath::new());
+    }
+
+    /// Iterate over `(path, value)` pairs in document order
+    ///
+    /// The root is yielded first with an empty path. Call
+    /// [`Walk::skip_children`] to prune the subtree of the value just yielded.
+    ///
+    /// ```
+    /// use gbln::parse;
+    ///
+    /// let value = parse("app{name<s8>(web)ports<u16>[80 443]}").unwrap();
+    /// let paths: Vec<String> = value.walk().map(|(p, _)| p.to_string()).collect();
+    /// assert_eq!(paths, ["", "app", "app.name", "app.ports", "app.ports[0]", "app.ports[1]"]);
+    /// ```
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            stack: vec![(ValuePath::new(), self)],
+            last: None,
+        }
+    }
+}
+
+/// Returns false if the traversal was stopped
+fn accept<V: Visitor + ?Sized>(value: &Value, visitor: &mut V, path: &mut ValuePath) -> bool {
+    match visitor.enter(path, value) {
+        Visit::Stop => return false,
+        Visit::SkipChildren => {}
+        Visit::Continue => match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    path.push(PathSegment::Key(key.clone()));
+                    let go_on = accept(child, visitor, path);
+                    path.pop();
+                    if !go_on {
+                        return false;
+                    }
+                }
+            }
+            Value::Array(arr) => {
+                for (i, child) in arr.iter().enumerate() {
+                    path.push(PathSegment::Index(i));
+                    let go_on = accept(child, visitor, path);
+                    path.pop();
+                    if !go_on {
+                        return false;
+                    }
+                }
+            }
+            _ => {}
+        },
+    }
+    visitor.leave(path, value);
+    true
+}
+
+/// Returns false if the traversal was stopped
+fn accept_mut<V: VisitorMut + ?Sized>(
+    value: &mut Value,
+    visitor: &mut V,
+    path: &mut ValuePath,
+) -> bool {
+    match visitor.enter(path, value) {
+        Visit::Stop => return false,
+        Visit::SkipChildren => {}
+        Visit::Continue => match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    path.push(PathSegment::Key(key.clone()));
+                    let go_on = accept_mut(child, visitor, path);
+                    path.pop();
+                    if !go_on {
+                        return false;
+                    }
+                }
+            }
+            Value::Array(arr) => {
+                for (i, child) in arr.iter_mut().enumerate() {
+                    path.push(PathSegment::Index(i));
+                    let go_on = accept_mut(child, visitor, path);
+                    path.pop();
+                    if !go_on {
+                        return false;
+                    }
+                }
+            }
+            _ => {}
+        },
+    }
+    visitor.leave(path, value);
+    true
+}
+
+/// Iterator returned by [`Value::walk`]
+pub struct Walk<'v> {
+    stack: Vec<(ValuePath, &'v Value)>,
+    last: Option<(ValuePath, &'v Value)>,
+}
+
+impl Walk<'_> {
+    /// Do not descend into the value most recently yielded
+    pub fn skip_children(&mut self) {
+        self.last = None;
+    }
+
+    /// Queue the children of the last yielded value, in reverse so the
+    /// first child is popped first
+    fn expand_last(&mut self) {
+        let Some((path, value)) = self.last.take() else {
+            return;
+        };
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter().rev() {
+                    let mut child_path = path.clone();
+                    child_path.push(PathSegment::Key(key.clone()));
+                    self.stack.push((child_path, child));
+                }
+            }
+            Value::Array(arr) => {
+                for (i, child) in arr.iter().enumerate().rev() {
+                    let mut child_path = path.clone();
+                    child_path.push(PathSegment::Index(i));
+                    self.stack.push((child_path, child));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'v> Iterator for Walk<'v> {
+    type Item = (ValuePath, &'v Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.expand_last();
+        let (path, value) = self.stack.pop()?;
+        self.last = Some((path.clone(), value));
+        Some((path, value))
+    }
+}
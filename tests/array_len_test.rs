@@ -0,0 +1,147 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for array length bounds
+
+use gbln::{parse, to_string, Declaration, ErrorKind, LenRange, Map, TypeHint, Value};
+
+#[test]
+fn t_parse_len_bounds() {
+    let value = parse("v{pos<f32;3>[1 2.5 -3] tags<s8;..4>[a b] ids<u8;1..>[7]}").unwrap();
+    assert_eq!(value["v"]["pos"].as_array().unwrap().len(), 3);
+    assert_eq!(value["v"]["tags"][1], Value::Str("b".to_string()));
+
+    let obj = value["v"].as_object().unwrap();
+    assert_eq!(
+        obj.declaration("pos"),
+        Some(&Declaration::Bounded(
+            Box::new(Declaration::TypedArray(TypeHint::F32)),
+            LenRange::exact(3)
+        ))
+    );
+    assert_eq!(
+        obj.declaration("ids"),
+        Some(&Declaration::Bounded(
+            Box::new(Declaration::TypedArray(TypeHint::U8)),
+            LenRange { min: 1, max: None }
+        ))
+    );
+
+    // Untyped arrays and tables take bounds too
+    let value = parse("v{items<;..2>[1 x] rows<{id<u8>};2>[(1)(2)]}").unwrap();
+    assert_eq!(value["v"]["items"][1], Value::Str("x".to_string()));
+    assert_eq!(value["v"]["rows"][1]["id"], Value::U8(2));
+}
+
+#[test]
+fn t_len_bounds_on_unkeyed_arrays() {
+    let value = parse("<u8;2>[1 2]").unwrap();
+    assert_eq!(value, Value::Array(vec![Value::U8(1), Value::U8(2)]));
+
+    let value = parse("v[<s8;..2>[a b] <;1..>[x] {w[<u8;1>[3]]}]").unwrap();
+    assert_eq!(value["v"][0][1], Value::Str("b".to_string()));
+    assert_eq!(value["v"][1][0], Value::Str("x".to_string()));
+    assert_eq!(value["v"][2]["w"][0][0], Value::U8(3));
+
+    for (input, kind) in [
+        ("<u8;2>[1 2 3]", ErrorKind::TooManyItems),
+        ("<u8;2>[1]", ErrorKind::TooFewItems),
+        ("<;..1>[x y]", ErrorKind::TooManyItems),
+        ("[<u8;..1>[1 2]]", ErrorKind::TooManyItems),
+        ("v[<;1..>[]]", ErrorKind::TooFewItems),
+        ("v{a[<s8;3>[x]]}", ErrorKind::TooFewItems),
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, kind, "{}", input);
+    }
+}
+
+#[test]
+fn t_too_many_items() {
+    for input in [
+        "v<u8;..3>[1 2 3 4]",
+        "v{a<u8;2>[1 2 3]}",
+        "v{a<;..1>[x y]}",
+        "v{a<{id<u8>};..1>[(1)(2)]}",
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TooManyItems, "{}", input);
+    }
+
+    // Parsing stops at the first element over the limit
+    let input = format!("v<u8;..2>[1 2 3 {}]", "x ".repeat(1000));
+    let err = parse(&input).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TooManyItems);
+    assert!(
+        err.message
+            .contains("more than 2 items (expected at most 2)"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn t_too_few_items() {
+    for input in [
+        "v<f32;3>[1 2]",
+        "v{a<s8;2..4>[x]}",
+        "v{a<;1..>[]}",
+        "v{a<{id<u8>};1..>[]}",
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TooFewItems, "{}", input);
+    }
+
+    let err = parse("v<f32;3>[1 2]").unwrap_err();
+    assert!(
+        err.message.contains("has 2 items (expected exactly 3)"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn t_invalid_len_bounds() {
+    for input in [
+        "v<u8;..>[]",
+        "v<u8;4..2>[]",
+        "v<u8;1..2..3>[]",
+        "v<u8;3>(1)",
+        "v[<u8;3>(1)]",
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidTypeHint, "{}", input);
+    }
+
+    // `;` inside a pattern is not a length
+    let value = parse("v<s8:/a;b/>(a;b)").unwrap();
+    assert_eq!(value["v"], Value::Str("a;b".to_string()));
+}
+
+#[test]
+fn t_serialize_len_bounds() {
+    for input in [
+        "{pos<f32;3>[1 2.5 -3]}",
+        "{tags<s8;..4>[a b]}",
+        "{items<;1..2>[1 x]}",
+        "{rows<{id<u8>};..10>[(1)(2)]}",
+    ] {
+        assert_eq!(to_string(&parse(input).unwrap()), input);
+    }
+
+    // Bounds the array has outgrown are dropped
+    let mut value = parse("{tags<s8;..2>[a b]}").unwrap();
+    if let Value::Object(obj) = &mut value {
+        if let Some(Value::Array(tags)) = obj.get_mut("tags") {
+            tags.push(Value::Str("c".to_string()));
+        }
+    }
+    let text = to_string(&value);
+    assert_eq!(text, "{tags<s8>[a b c]}");
+    assert_eq!(parse(&text).unwrap(), value);
+
+    // Arrays built in code have no bounds
+    let mut obj = Value::Object(Map::new());
+    obj.insert("pos", vec![Value::F32(1.0), Value::F32(2.0)]);
+    assert!(!to_string(&obj).contains(';'));
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for binary values
+
+use gbln::{parse, to_string, ErrorKind, TypeHint, Value};
+
+#[test]
+fn t_binary_type_hints() {
+    assert_eq!(TypeHint::from_str("bin").unwrap(), TypeHint::Bin(None));
+    assert_eq!(
+        TypeHint::from_str("bin:32").unwrap(),
+        TypeHint::Bin(Some(32))
+    );
+    assert_eq!(TypeHint::Bin(Some(32)).as_str(), "bin:32");
+    assert!(TypeHint::from_str("bin:x").is_err());
+}
+
+#[test]
+fn t_parse_base64() {
+    let value = parse("file{hash<bin>(3q2+7w==)empty<bin>()}").unwrap();
+
+    assert_eq!(
+        value["file"]["hash"].as_bytes(),
+        Some(&[0xde, 0xad, 0xbe, 0xef][..])
+    );
+    assert_eq!(value["file"]["empty"], Value::Bytes(Vec::new()));
+    assert_eq!(value["file"]["hash"].type_name(), "bytes");
+}
+
+#[test]
+fn t_parse_base64_unpadded_and_hex() {
+    let padded = TypeHint::Bin(None).parse_value("aGk=").unwrap();
+
+    assert_eq!(TypeHint::Bin(None).parse_value("aGk").unwrap(), padded);
+    assert_eq!(TypeHint::Bin(None).parse_value("hex:6869").unwrap(), padded);
+    assert_eq!(
+        TypeHint::Bin(None).parse_value("hex:6A6b").unwrap(),
+        Value::Bytes(b"jk".to_vec())
+    );
+}
+
+#[test]
+fn t_parse_binary_invalid() {
+    for content in ["a", "aGk==", "a=Gk", "aGl=", "aG!k", "hex:abc", "hex:zz"] {
+        let err = TypeHint::Bin(None).parse_value(content).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TypeMismatch, "content {:?}", content);
+    }
+}
+
+#[test]
+fn t_binary_max_length() {
+    let value = parse("key{sig<bin:4>(3q2+7w==)}").unwrap();
+    assert_eq!(value["key"]["sig"].as_bytes().map(<[u8]>::len), Some(4));
+
+    let err = parse("key{sig<bin:3>(3q2+7w==)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::BytesTooLong);
+    assert!(err.message.contains("4 bytes (max 3)"));
+}
+
+#[test]
+fn t_serialize_binary_as_base64() {
+    let value = Value::Bytes((0u8..=255).collect());
+    let serialized = to_string(&value);
+
+    assert!(serialized.starts_with("<bin>(AAECAwQF"));
+    assert!(serialized.ends_with("/w==)"));
+    assert_eq!(parse(&serialized).unwrap(), value);
+
+    // Hex input is re-emitted as base64 under its declared hint
+    let parsed = parse("key{sig<bin:8>(hex:deadbeef)}").unwrap();
+    assert_eq!(to_string(&parsed), "{key{sig<bin:8>(3q2+7w==)}}");
+}
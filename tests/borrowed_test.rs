@@ -0,0 +1,96 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for zero-copy borrowed parsing
+
+use gbln::borrowed::Value as BorrowedValue;
+use gbln::{parse, parse_borrowed, to_string_pretty, Value};
+use std::borrow::Cow;
+
+const INPUT: &str = "app{name<s32>(My App)version(1.0.0)port<u16>(8080)tags[web api]}";
+
+#[test]
+fn t_keys_and_strings_borrow_from_input() {
+    let value = parse_borrowed(INPUT).unwrap();
+    let app = value["app"].as_object().unwrap();
+
+    for (key, _) in app {
+        assert!(matches!(key, Cow::Borrowed(_)), "key {:?} was copied", key);
+    }
+    assert!(matches!(
+        value["app"]["name"],
+        BorrowedValue::Str(Cow::Borrowed("My App"))
+    ));
+    assert!(matches!(
+        value["app"]["version"],
+        BorrowedValue::Str(Cow::Borrowed(_))
+    ));
+    assert!(matches!(
+        value["app"]["tags"].as_array().unwrap()[0],
+        BorrowedValue::Str(Cow::Borrowed("web"))
+    ));
+}
+
+#[test]
+fn t_borrowed_slices_point_into_input() {
+    let value = parse_borrowed(INPUT).unwrap();
+    let name = value["app"]["name"].as_str().unwrap();
+
+    let input_range = INPUT.as_ptr() as usize..INPUT.as_ptr() as usize + INPUT.len();
+    assert!(input_range.contains(&(name.as_ptr() as usize)));
+}
+
+#[test]
+fn t_scalars_are_inline() {
+    let value = parse_borrowed(INPUT).unwrap();
+    assert_eq!(value["app"]["port"], BorrowedValue::U16(8080));
+}
+
+#[test]
+fn t_into_owned_matches_parse() {
+    let borrowed = parse_borrowed(INPUT).unwrap();
+    let owned: Value = borrowed.into_owned();
+
+    assert_eq!(owned, parse(INPUT).unwrap());
+    assert_eq!(owned["app"]["name"].as_str(), Some("My App"));
+}
+
+#[test]
+fn t_parse_keeps_field_metadata() {
+    // `parse` builds owned values directly; declarations and docs must match
+    // those of a detached borrowed tree
+    let input = "app{
+  :|| Listening port
+  port<u16>(8080)
+  hosts<s16;1..>[a b]
+  users<{id<u32> name<s8>}>[(1 Ann)]
+  \"display name\"(x)
+}";
+    let owned = parse(input).unwrap();
+    let detached = parse_borrowed(input).unwrap().into_owned();
+
+    assert_eq!(owned, detached);
+    assert_eq!(to_string_pretty(&owned), to_string_pretty(&detached));
+    assert_eq!(
+        owned["app"].as_object().unwrap().doc("port"),
+        Some("Listening port")
+    );
+}
+
+#[test]
+fn t_borrowed_validation_errors() {
+    assert!(parse_borrowed("name<s4>(Alice)").is_err());
+    assert!(parse_borrowed("count<u8>(300)").is_err());
+}
+
+#[test]
+fn t_owned_value_converts_to_borrowed() {
+    let owned = parse(INPUT).unwrap();
+    let borrowed = BorrowedValue::from(owned.clone());
+
+    assert!(matches!(
+        borrowed["app"]["name"],
+        BorrowedValue::Str(Cow::Owned(_))
+    ));
+    assert_eq!(borrowed.into_owned(), owned);
+}
@@ -6,6 +6,7 @@
 use gbln::GblnConfig;
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn t_default_config() {
     let config = GblnConfig::default();
     assert_eq!(config.mini_mode, true);
@@ -16,6 +17,7 @@ fn t_default_config() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn t_development_config() {
     let config = GblnConfig::development();
     assert_eq!(config.mini_mode, false);
@@ -25,6 +27,7 @@ fn t_development_config() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn t_io_format_config() {
     let config = GblnConfig::io_format();
     assert_eq!(config.mini_mode, true);
@@ -33,6 +36,7 @@ fn t_io_format_config() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn t_config_presets() {
     let dev = GblnConfig::development();
     assert_eq!(dev.mini_mode, false);
@@ -47,6 +51,7 @@ fn t_config_presets() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn t_builder_pattern() {
     let config = GblnConfig::new()
         .mini(false)
@@ -0,0 +1,143 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for 128-bit integers and exact decimals
+
+use gbln::{parse, to_string, Decimal, ErrorKind, Map, TypeHint, Value};
+
+#[test]
+fn t_parse_decimal() {
+    let d: Decimal = "-12.50".parse().unwrap();
+    assert_eq!((d.mantissa(), d.scale()), (-1250, 2));
+    assert_eq!(d.to_string(), "-12.50");
+    assert_eq!("0.05".parse::<Decimal>().unwrap().to_string(), "0.05");
+    assert_eq!("-0.5".parse::<Decimal>().unwrap().to_string(), "-0.5");
+    assert_eq!("42".parse::<Decimal>().unwrap().scale(), 0);
+
+    for invalid in ["", "-", ".5", "1.", "1.2.3", "1e5", "abc", "1,5"] {
+        assert!(invalid.parse::<Decimal>().is_err(), "decimal {:?}", invalid);
+    }
+
+    let too_long = "1".repeat(39);
+    let err = too_long.parse::<Decimal>().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueOutOfRange);
+}
+
+#[test]
+fn t_decimal_equality_and_ordering() {
+    let a: Decimal = "1.5".parse().unwrap();
+    let b: Decimal = "1.50".parse().unwrap();
+    let c: Decimal = "-2.25".parse().unwrap();
+
+    // Structural equality keeps the scale, semantic equality does not
+    assert_ne!(a, b);
+    assert!(Value::Decimal(a).semantic_eq(&Value::Decimal(b)));
+    assert!(Value::Decimal("3.00".parse().unwrap()).semantic_eq(&Value::I64(3)));
+    assert!(!Value::Decimal(a).semantic_eq(&Value::F64(1.5)));
+
+    let mut sorted = vec![b, c, a];
+    sorted.sort();
+    assert_eq!(sorted, vec![c, a, b]);
+    assert_eq!(a.with_scale(3).unwrap().to_string(), "1.500");
+    assert_eq!(b.with_scale(1), Some(a));
+    assert_eq!("1.25".parse::<Decimal>().unwrap().with_scale(1), None);
+}
+
+#[test]
+fn t_decimal_type_hint() {
+    let hint = TypeHint::from_str("dec:10.2").unwrap();
+    assert_eq!(hint, TypeHint::Decimal(10, 2));
+    assert_eq!(hint.as_str(), "dec:10.2");
+
+    for invalid in ["dec:0.0", "dec:39.2", "dec:4.5", "dec:10", "dec:a.b"] {
+        let err = TypeHint::from_str(invalid).unwrap_err();
+        assert!(err.contains("Invalid decimal type"), "{}: {}", invalid, err);
+    }
+
+    // Values are stored at the declared scale
+    let value = hint.parse_value("19.9").unwrap();
+    assert_eq!(value.as_decimal().unwrap().to_string(), "19.90");
+    assert_eq!(hint.parse_value("19.900").unwrap(), value);
+
+    let err = hint.parse_value("19.999").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+
+    let err = hint.parse_value("123456789").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueOutOfRange);
+    assert!(hint.parse_value("12345678.99").is_ok());
+}
+
+#[test]
+fn t_parse_decimal_field() {
+    let value = parse("order{total<dec:10.2>(1299.5) tax<dec:4.4?>()}").unwrap();
+    assert_eq!(
+        value["order"]["total"].as_decimal().unwrap().to_string(),
+        "1299.50"
+    );
+    assert!(value["order"]["tax"].is_null());
+
+    let err = parse("order{total<dec:5.2>(1299.5)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueOutOfRange);
+    assert_eq!(err.line, 1);
+}
+
+#[test]
+fn t_parse_128_bit_integers() {
+    let value = parse(&format!(
+        "ids{{min<i128>({}) max<u128>({})}}",
+        i128::MIN,
+        u128::MAX
+    ))
+    .unwrap();
+    assert_eq!(value["ids"]["min"], Value::I128(i128::MIN));
+    assert_eq!(value["ids"]["max"], Value::U128(u128::MAX));
+    assert_eq!(value["ids"]["max"].as_u128(), Some(u128::MAX));
+    assert_eq!(value["ids"]["max"].as_i128(), None);
+
+    let err = parse("n<u128>(-1)").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+    let err = parse(&format!("n<u128>({}0)", u128::MAX)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+    let err = parse(&format!("n<i128>({}0)", i128::MAX)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+    let err = parse("n<i128>(1.5)").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+}
+
+#[test]
+fn t_infer_large_integers() {
+    let value = parse(&format!(
+        "n{{a({}) b({}) c({}) d({}0)}}",
+        u64::MAX,
+        i128::MIN,
+        u128::MAX,
+        u128::MAX
+    ))
+    .unwrap();
+    assert_eq!(value["n"]["a"], Value::U64(u64::MAX));
+    assert_eq!(value["n"]["b"], Value::I128(i128::MIN));
+    assert_eq!(value["n"]["c"], Value::U128(u128::MAX));
+
+    // Too large for any integer type: kept verbatim rather than rounded
+    assert_eq!(
+        value["n"]["d"],
+        Value::Str(format!("{}0", u128::MAX)),
+        "oversized integer literal must not become a float"
+    );
+}
+
+#[test]
+fn t_round_trip_wide_numbers() {
+    let mut obj = Value::Object(Map::new());
+    obj.insert("big", u128::MAX);
+    obj.insert("neg", i128::MIN);
+    obj.insert("price", "0.05".parse::<Decimal>().unwrap());
+
+    let text = to_string(&obj);
+    assert!(text.contains("price<dec:2.2>(0.05)"), "{}", text);
+    assert_eq!(parse(&text).unwrap(), obj);
+
+    // Declared decimal hints are kept
+    let input = "{price<dec:10.2>(5.00)}";
+    assert_eq!(to_string(&parse(input).unwrap()), input);
+}
@@ -0,0 +1,230 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for strict and lenient literal dialects
+
+use gbln::{
+    parse, parse_with_options, to_string, Dialect, ErrorKind, ParseOptions, TypeHint, Value,
+};
+
+#[test]
+fn t_default_dialect_is_lenient() {
+    assert_eq!(ParseOptions::default().dialect, Dialect::Lenient);
+    assert_eq!(ParseOptions::strict().dialect, Dialect::Strict);
+    assert_eq!(parse("a<b>(yes)").unwrap()["a"], Value::Bool(true));
+}
+
+#[test]
+fn t_typed_booleans() {
+    let strict = ParseOptions::strict();
+    let lenient = ParseOptions::lenient();
+
+    for (text, expected) in [("t", true), ("true", true), ("f", false), ("false", false)] {
+        for options in [&strict, &lenient] {
+            assert_eq!(
+                TypeHint::Bool
+                    .parse_value_with_options(text, options)
+                    .unwrap(),
+                Value::Bool(expected)
+            );
+        }
+    }
+
+    for (text, expected) in [("1", true), ("yes", true), ("0", false), ("no", false)] {
+        assert_eq!(
+            TypeHint::Bool
+                .parse_value_with_options(text, &lenient)
+                .unwrap(),
+            Value::Bool(expected)
+        );
+        let err = TypeHint::Bool
+            .parse_value_with_options(text, &strict)
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TypeMismatch);
+    }
+}
+
+#[test]
+fn t_typed_nulls() {
+    let strict = ParseOptions::strict();
+    let lenient = ParseOptions::lenient();
+
+    for text in ["", "null"] {
+        assert!(TypeHint::Null
+            .parse_value_with_options(text, &strict)
+            .is_ok());
+    }
+    assert!(TypeHint::Null
+        .parse_value_with_options("n", &strict)
+        .is_err());
+    assert_eq!(
+        TypeHint::Null
+            .parse_value_with_options("n", &lenient)
+            .unwrap(),
+        Value::Null
+    );
+
+    // The same spellings apply to nullable hints
+    let value = parse_with_options("a{port<u16?>(n)}", &lenient).unwrap();
+    assert!(value["a"]["port"].is_null());
+    assert!(parse_with_options("a{port<u16?>(n)}", &strict).is_err());
+}
+
+#[test]
+fn t_typed_number_notation() {
+    let strict = ParseOptions::strict();
+
+    for (hint, text) in [
+        ("f64", "-3.25"),
+        ("f32", "12"),
+        ("f64", "1e300"),
+        ("f64", "-2.5e-8"),
+        ("i32", "-7"),
+        ("u128", "42"),
+    ] {
+        let hint = TypeHint::from_str(hint).unwrap();
+        assert!(
+            hint.parse_value_with_options(text, &strict).is_ok(),
+            "{}",
+            text
+        );
+    }
+
+    for (hint, text) in [
+        ("f64", "1E5"),
+        ("f64", "1e+5"),
+        ("f64", "infinity"),
+        ("f64", "NaN"),
+        ("f64", ".5"),
+        ("i32", "+7"),
+    ] {
+        let hint = TypeHint::from_str(hint).unwrap();
+        assert!(hint.parse_value(text).is_ok(), "lenient {}", text);
+        let err = hint.parse_value_with_options(text, &strict).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TypeMismatch);
+        assert!(err.message.contains("strict dialect"), "{}", err.message);
+    }
+}
+
+#[test]
+fn t_default_inference_keeps_aliases_as_strings() {
+    assert_eq!(parse("a(yes)").unwrap()["a"], Value::Str("yes".to_string()));
+    assert_eq!(parse("a(no)").unwrap()["a"], Value::Str("no".to_string()));
+    assert_eq!(parse("a(n)").unwrap()["a"], Value::Str("n".to_string()));
+    assert_eq!(
+        parse_with_options("a(yes)", &ParseOptions::new()).unwrap()["a"],
+        Value::Str("yes".to_string())
+    );
+
+    // Typed values take the aliases as before
+    assert_eq!(parse("a<b>(no)").unwrap()["a"], Value::Bool(false));
+
+    // Opt in explicitly
+    let options = ParseOptions::new().infer_aliases(true);
+    assert_eq!(
+        parse_with_options("a(yes)", &options).unwrap()["a"],
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn t_default_options_split_typed_and_untyped_aliases() {
+    // Both report the lenient dialect, but only `lenient()` infers aliases
+    let default = ParseOptions::new();
+    let lenient = ParseOptions::lenient();
+    assert_eq!(default.dialect, Dialect::Lenient);
+    assert_eq!(lenient.dialect, Dialect::Lenient);
+    assert!(!default.infer_aliases);
+    assert!(lenient.infer_aliases);
+
+    let input = "v{typed<b>(yes) untyped(yes) none<n>(n) maybe(n)}";
+    let value = parse_with_options(input, &default).unwrap();
+    assert_eq!(value["v"]["typed"], Value::Bool(true));
+    assert_eq!(value["v"]["untyped"], Value::Str("yes".to_string()));
+    assert_eq!(value["v"]["none"], Value::Null);
+    assert_eq!(value["v"]["maybe"], Value::Str("n".to_string()));
+
+    let value = parse_with_options(input, &lenient).unwrap();
+    assert_eq!(value["v"]["untyped"], Value::Bool(true));
+    assert_eq!(value["v"]["maybe"], Value::Null);
+}
+
+#[test]
+fn t_inference_follows_dialect() {
+    let input = "v{a(yes) b(n) c(1e5) d(inf) e(1) f(t) g(+5) h(-2.5) i(1E5)}";
+
+    let lenient = parse_with_options(input, &ParseOptions::lenient()).unwrap();
+    assert_eq!(lenient["v"]["a"], Value::Bool(true));
+    assert_eq!(lenient["v"]["b"], Value::Null);
+    assert_eq!(lenient["v"]["c"], Value::F64(100000.0));
+    assert_eq!(lenient["v"]["d"], Value::F64(f64::INFINITY));
+    assert_eq!(lenient["v"]["e"], Value::I64(1));
+    assert_eq!(lenient["v"]["f"], Value::Bool(true));
+    assert_eq!(lenient["v"]["g"], Value::I64(5));
+    assert_eq!(lenient["v"]["h"], Value::F64(-2.5));
+    assert_eq!(lenient["v"]["i"], Value::F64(100000.0));
+
+    // Strict keeps non-canonical spellings as strings
+    let strict = parse_with_options(input, &ParseOptions::strict()).unwrap();
+    assert_eq!(strict["v"]["a"], Value::Str("yes".to_string()));
+    assert_eq!(strict["v"]["b"], Value::Str("n".to_string()));
+    assert_eq!(strict["v"]["c"], Value::F64(100000.0));
+    assert_eq!(strict["v"]["d"], Value::Str("inf".to_string()));
+    assert_eq!(strict["v"]["e"], Value::I64(1));
+    assert_eq!(strict["v"]["f"], Value::Bool(true));
+    assert_eq!(strict["v"]["g"], Value::Str("+5".to_string()));
+    assert_eq!(strict["v"]["h"], Value::F64(-2.5));
+    assert_eq!(strict["v"]["i"], Value::Str("1E5".to_string()));
+}
+
+#[test]
+fn t_serializer_output_reads_back_in_both_dialects() {
+    let mut obj = Value::Object(Default::default());
+    obj.insert("answer", "yes");
+    obj.insert("initial", "n");
+    obj.insert("exp", "1e5");
+    obj.insert("upper", "1E5");
+    for (i, text) in [
+        "+5",
+        "inf",
+        "NaN",
+        "-inf",
+        ".5",
+        "1.",
+        "0",
+        "1",
+        "no",
+        "true",
+        "null",
+        "",
+        "-",
+        "1.2.3",
+        "0x10",
+        "99999999999999999999999999999999999999999999",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        obj.insert(format!("s{}", i), text);
+    }
+    obj.insert("f", Value::F64(1e300));
+    obj.insert("g", Value::F64(f64::INFINITY));
+    obj.insert("h", Value::U8(5));
+    obj.insert("list", vec!["n", "yes", "+1", "x"]);
+    obj.insert("maybe", Value::Null);
+    let text = to_string(&obj);
+
+    for options in [ParseOptions::strict(), ParseOptions::lenient()] {
+        assert_eq!(
+            parse_with_options(&text, &options).unwrap(),
+            obj,
+            "{}",
+            text
+        );
+    }
+
+    // Declared forms that would read differently are re-typed
+    let parsed = parse_with_options("a{tag<s4?>(n)}", &ParseOptions::strict()).unwrap();
+    let text = to_string(&parsed);
+    assert_eq!(parse(&text).unwrap(), parsed, "{}", text);
+}
@@ -0,0 +1,145 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for field doc comments
+
+use gbln::{
+    parse, parse_reader, to_string, to_string_pretty, to_string_with_config, GblnConfig, Map, Value,
+};
+
+const INPUT: &str = "\
+:| Not a doc comment
+:|| Application settings
+app{
+  :|| Port the server listens on.
+  :||
+  :|| Ports below 1024 need root.
+  port<u16>(8080)
+  :||| section separator, not a doc comment
+  host(localhost)
+  :|| Database connection
+  db{
+    :|| Pool size
+    pool<u8>(4)
+  }
+  tags[a :|| not attached to array elements
+    b]
+  :|| Trailing doc comments document nothing
+}";
+
+#[test]
+fn t_parse_doc_comments() {
+    let value = parse(INPUT).unwrap();
+
+    let root = value.as_object().unwrap();
+    assert_eq!(root.doc("app"), Some("Application settings"));
+
+    let app = value["app"].as_object().unwrap();
+    assert_eq!(
+        app.doc("port"),
+        Some("Port the server listens on.\n\nPorts below 1024 need root.")
+    );
+    assert_eq!(app.doc("host"), None);
+    assert_eq!(app.doc("db"), Some("Database connection"));
+    assert_eq!(app.doc("tags"), None);
+    assert_eq!(
+        value["app"]["db"].as_object().unwrap().doc("pool"),
+        Some("Pool size")
+    );
+
+    // Docs are metadata: values and equality are unaffected
+    assert_eq!(value, parse(&INPUT.replace(":||", ":|")).unwrap());
+}
+
+#[test]
+fn t_doc_comments_in_anonymous_objects() {
+    let value = parse("{\n  :|| First\n  a(1) b(2) :|| Second\n  c(3)\n}").unwrap();
+    let obj = value.as_object().unwrap();
+    assert_eq!(obj.doc("a"), Some("First"));
+    assert_eq!(obj.doc("b"), None);
+    assert_eq!(obj.doc("c"), Some("Second"));
+    assert_eq!(obj.meta("c").unwrap().doc.as_deref(), Some("Second"));
+}
+
+#[test]
+fn t_pretty_output_keeps_doc_comments() {
+    let value = parse(INPUT).unwrap();
+    let text = to_string_pretty(&value);
+    assert_eq!(
+        text,
+        "{
+  :|| Application settings
+  app{
+    :|| Port the server listens on.
+    :||
+    :|| Ports below 1024 need root.
+    port<u16>(8080)
+    host(localhost)
+    :|| Database connection
+    db{
+      :|| Pool size
+      pool<u8>(4)
+    }
+    tags[a b]
+  }
+}"
+    );
+
+    // Re-parsing gives back the same docs
+    let reparsed = parse(&text).unwrap();
+    assert_eq!(to_string_pretty(&reparsed), text);
+
+    // Pretty output is development output, so stripping comments applies
+    let config = GblnConfig::development();
+    assert_eq!(to_string_with_config(&value, &config).unwrap(), text);
+    let stripped = to_string_with_config(&value, &config.strip_comments(true)).unwrap();
+    assert!(!stripped.contains(":||"), "{}", stripped);
+    let without_docs: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with(":||"))
+        .collect();
+    assert_eq!(stripped, without_docs.join("\n"));
+}
+
+#[test]
+fn t_strip_comments_drops_doc_comments() {
+    let value = parse(INPUT).unwrap();
+
+    let text = to_string_with_config(&value, &GblnConfig::development()).unwrap();
+    assert!(text.contains(":|| Pool size"), "{}", text);
+
+    let config = GblnConfig::development().strip_comments(true);
+    let text = to_string_with_config(&value, &config).unwrap();
+    assert!(!text.contains(":|"), "{}", text);
+
+    // Compact output has no room for comments
+    assert!(!to_string(&value).contains(":|"));
+    let config = GblnConfig::new().strip_comments(false);
+    assert!(!to_string_with_config(&value, &config)
+        .unwrap()
+        .contains(":|"));
+}
+
+#[test]
+fn t_doc_comments_set_in_code() {
+    let mut map = Map::new();
+    map.insert("retries".to_string(), Value::U8(3));
+    map.meta_mut("retries").unwrap().doc = Some("How often to retry".to_string());
+    let value = Value::Object(map);
+
+    let text = to_string_pretty(&value);
+    assert_eq!(text, "{\n  :|| How often to retry\n  retries<u8>(3)\n}");
+    assert_eq!(
+        parse(&text).unwrap().as_object().unwrap().doc("retries"),
+        Some("How often to retry")
+    );
+}
+
+#[test]
+fn t_streamed_input_keeps_doc_comments() {
+    let value = parse_reader(INPUT.as_bytes()).unwrap();
+    assert_eq!(
+        value["app"].as_object().unwrap().doc("db"),
+        Some("Database connection")
+    );
+}
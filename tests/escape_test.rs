@@ -0,0 +1,151 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for escape sequences in values
+
+use gbln::{parse, parse_borrowed, to_string, to_string_pretty, ErrorKind, Map, Value};
+use std::borrow::Cow;
+
+/// Strings that cannot be written between parentheses as they are
+const AWKWARD: [&str; 16] = [
+    ":)",
+    "foo (bar",
+    ")(",
+    "(balanced)",
+    "a\\b",
+    "\\)",
+    "C:\\Users\\x",
+    "line1\nline2",
+    "crlf\r\n",
+    "\ttab",
+    " leading",
+    "trailing ",
+    "",
+    "bell\u{7}",
+    "emoji 😀",
+    "null",
+];
+
+#[test]
+fn t_parse_escapes() {
+    let value = parse(r"note<s64>(a \( b \) c \\ d\ne\tf\u{1F600}\u{e9})").unwrap();
+    assert_eq!(
+        value["note"],
+        Value::Str("a ( b ) c \\ d\ne\tf😀é".to_string())
+    );
+
+    // Untyped values are inferred after unescaping
+    let value = parse(r"v{a(\u{31}2) b(:\))}").unwrap();
+    assert_eq!(value["v"]["a"], Value::I64(12));
+    assert_eq!(value["v"]["b"], Value::Str(":)".to_string()));
+}
+
+#[test]
+fn t_whitespace_is_literal() {
+    let value = parse("v{a<s16>(  padded  ) b<s16>(two\nlines)}").unwrap();
+    assert_eq!(value["v"]["a"], Value::Str("  padded  ".to_string()));
+    assert_eq!(value["v"]["b"], Value::Str("two\nlines".to_string()));
+}
+
+#[test]
+fn t_invalid_escapes() {
+    for input in [
+        r"v<s8>(\u{110000})",
+        r"v<s8>(\u{d800})",
+        r"v<s8>(\u{})",
+        r"v(\u{zz})",
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidEscape, "{}", input);
+        // Reported where the value starts
+        let column = input.find('(').unwrap() + 2;
+        assert_eq!((err.line, err.column), (1, column), "{}", input);
+    }
+
+    // An escaped closing parenthesis does not end the value
+    let err = parse(r"v<s8>(abc\)").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn t_unknown_escapes_are_literal() {
+    let value = parse(r"v{path<s32>(C:\Users\ann\x.txt) re(\d+\.\w) u(\u41)}").unwrap();
+    assert_eq!(
+        value["v"]["path"],
+        Value::Str(r"C:\Users\ann\x.txt".to_string())
+    );
+    assert_eq!(value["v"]["re"], Value::Str(r"\d+\.\w".to_string()));
+    assert_eq!(value["v"]["u"], Value::Str(r"\u41".to_string()));
+
+    // Written back with the backslashes escaped, reading the same
+    let text = to_string(&value);
+    assert!(text.contains(r"C:\\Users"), "{}", text);
+    assert_eq!(parse(&text).unwrap(), value);
+}
+
+#[test]
+fn t_borrowed_values_copy_only_when_escaped() {
+    let value = parse_borrowed(r"v{a<s8>(plain) b<s8>(a\)b)}").unwrap();
+    assert!(matches!(
+        value["v"]["a"],
+        gbln::borrowed::Value::Str(Cow::Borrowed("plain"))
+    ));
+    assert!(matches!(
+        &value["v"]["b"],
+        gbln::borrowed::Value::Str(Cow::Owned(s)) if s == "a)b"
+    ));
+}
+
+#[test]
+fn t_serializer_escapes_only_when_needed() {
+    let mut obj = Value::Object(Map::new());
+    obj.insert("a", ":)");
+    obj.insert("b", "f(x)");
+    obj.insert("c", "x\ny");
+    obj.insert("d", "a\\b");
+    assert_eq!(
+        to_string(&obj),
+        r"{a<s2>(:\))b<s4>(f(x))c<s4>(x\ny)d<s4>(a\\b)}"
+    );
+}
+
+#[test]
+fn t_every_string_round_trips() {
+    let mut obj = Value::Object(Map::new());
+    for (i, s) in AWKWARD.iter().enumerate() {
+        obj.insert(format!("k{}", i), *s);
+    }
+    obj.insert(
+        "list",
+        AWKWARD.iter().map(|s| Value::from(*s)).collect::<Vec<_>>(),
+    );
+    obj.insert("words", vec!["a b", "c"]);
+
+    // Longer than the largest rounded hint
+    let long = "é(".repeat(1000);
+    obj.insert("long", long.as_str());
+    obj.insert("long_list", vec!["x".repeat(1500), "y".to_string()]);
+
+    for text in [to_string(&obj), to_string_pretty(&obj)] {
+        assert_eq!(parse(&text).unwrap(), obj, "{}", text);
+    }
+
+    // Top-level arrays
+    let arr = Value::Array(AWKWARD.iter().map(|s| Value::from(*s)).collect());
+    assert_eq!(parse(&to_string(&arr)).unwrap(), arr);
+    let arr = Value::Array(vec!["a b".into(), "c".into()]);
+    assert_eq!(parse(&to_string(&arr)).unwrap(), arr);
+    let arr = Value::Array(vec![long.as_str().into()]);
+    assert_eq!(parse(&to_string(&arr)).unwrap(), arr);
+
+    // Declared forms escape as well
+    let mut declared = parse("{a(x) b<s64>(x)}").unwrap();
+    for s in AWKWARD {
+        if let Value::Object(map) = &mut declared {
+            *map.get_mut("a").unwrap() = Value::from(s);
+            *map.get_mut("b").unwrap() = Value::from(s);
+        }
+        let text = to_string(&declared);
+        assert_eq!(parse(&text).unwrap(), declared, "{}", text);
+    }
+}
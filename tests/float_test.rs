@@ -0,0 +1,139 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for float formatting and non-finite values
+
+use gbln::{
+    parse, parse_with_options, to_string, to_string_with_config, ErrorKind, GblnConfig, Map,
+    ParseOptions, Value,
+};
+
+/// Serialise a single field and parse it back
+fn round_trip(value: Value) -> Value {
+    let mut obj = Value::Object(Map::new());
+    obj.insert("x", value);
+    let text = to_string(&obj);
+    parse_with_options(&text, &ParseOptions::strict()).unwrap()["x"].clone()
+}
+
+#[test]
+fn t_f32_round_trips_bit_exactly() {
+    for n in [
+        0.1f32,
+        1.0 / 3.0,
+        -0.0,
+        16_777_217.0,
+        f32::MAX,
+        f32::MIN,
+        f32::MIN_POSITIVE,
+        f32::from_bits(1),
+        f32::EPSILON,
+    ] {
+        let back = round_trip(Value::F32(n)).as_f32().unwrap();
+        assert_eq!(back.to_bits(), n.to_bits(), "{:?}", n);
+    }
+}
+
+#[test]
+fn t_f64_round_trips_bit_exactly() {
+    for n in [
+        0.1f64 + 0.2,
+        1.0 / 3.0,
+        -0.0,
+        9_007_199_254_740_993.0,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        f64::from_bits(1),
+        1e300,
+        -2.5e-8,
+    ] {
+        let back = round_trip(Value::F64(n)).as_f64().unwrap();
+        assert_eq!(back.to_bits(), n.to_bits(), "{:?}", n);
+    }
+}
+
+#[test]
+fn t_floats_use_shortest_notation() {
+    let value = parse("{a<f32>(0.1)b<f64>(0.1)c<f32>(16777216)d<f64>(0.025)}").unwrap();
+    assert_eq!(
+        to_string(&value),
+        "{a<f32>(0.1)b<f64>(0.1)c<f32>(16777216)d<f64>(0.025)}"
+    );
+
+    // Exponent notation only where it is shorter
+    let value = parse("{a<f64>(1.5e3)b<f32>(100000)c<f64>(0.000001)d<f32>(-2.5e-8)}").unwrap();
+    assert_eq!(
+        to_string(&value),
+        "{a<f64>(1500)b<f32>(1e5)c<f64>(1e-6)d<f32>(-2.5e-8)}"
+    );
+}
+
+#[test]
+fn t_extreme_floats_stay_short() {
+    for (value, text) in [
+        (Value::F64(1e300), "1e300"),
+        (Value::F64(-1e300), "-1e300"),
+        (Value::F64(5e-324), "5e-324"),
+        (Value::F64(f64::MAX), "1.7976931348623157e308"),
+        (Value::F32(f32::MAX), "3.4028235e38"),
+        (Value::F32(f32::MIN_POSITIVE), "1.1754944e-38"),
+        (Value::F32(f32::from_bits(1)), "1e-45"),
+    ] {
+        let mut obj = Value::Object(Map::new());
+        obj.insert("x", value.clone());
+        let out = to_string(&obj);
+        assert!(out.contains(&format!("({})", text)), "{}", out);
+        assert!(out.len() < 40, "{}", out);
+
+        for options in [ParseOptions::strict(), ParseOptions::lenient()] {
+            assert_eq!(parse_with_options(&out, &options).unwrap(), obj, "{}", out);
+        }
+    }
+}
+
+#[test]
+fn t_non_finite_floats_are_typed() {
+    let mut obj = Value::Object(Map::new());
+    obj.insert("nan", f64::NAN);
+    obj.insert("inf", f32::INFINITY);
+    obj.insert("neg", f64::NEG_INFINITY);
+    let text = to_string(&obj);
+    assert_eq!(text, "{nan<f64>(nan)inf<f32>(inf)neg<f64>(-inf)}");
+
+    for options in [ParseOptions::strict(), ParseOptions::lenient()] {
+        assert_eq!(parse_with_options(&text, &options).unwrap(), obj);
+    }
+}
+
+#[test]
+fn t_reject_non_finite_on_parse() {
+    let options = ParseOptions::new().allow_non_finite(false);
+
+    for input in [
+        "x<f64>(inf)",
+        "x<f32>(nan)",
+        "x<f64?>(-inf)",
+        "x(NaN)",
+        "x[1.5 inf]",
+    ] {
+        let err = parse_with_options(input, &options).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NonFiniteFloat, "{}", input);
+        assert!(err.message.contains("is not allowed"), "{}", err.message);
+    }
+
+    let value = parse_with_options("x<f64>(1.5)", &options).unwrap();
+    assert_eq!(value["x"], Value::F64(1.5));
+}
+
+#[test]
+fn t_reject_non_finite_on_serialize() {
+    let value = parse("sensor{temp<f32>(21.5)readings<f64>[1.5 nan]}").unwrap();
+    let config = GblnConfig::new().allow_non_finite(false);
+
+    let err = to_string_with_config(&value, &config).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NonFiniteFloat);
+    assert!(err.message.contains("'nan' at 'sensor.readings[1]'"));
+
+    let finite = parse("sensor{temp<f32>(21.5)}").unwrap();
+    assert!(to_string_with_config(&finite, &config).is_ok());
+}
@@ -5,7 +5,7 @@
 //!
 //! Tests write_io() and read_io() functions with various configurations.
 
-use gbln::{parse, read_io, write_io, GblnConfig};
+use gbln::{parse, read_io, write_io, ErrorKind, GblnConfig, Value};
 use std::fs;
 use std::path::Path;
 
@@ -297,3 +297,43 @@ fn t_roundtrip_complex_nested_structure() {
     // Cleanup
     fs::remove_file(path).ok();
 }
+
+#[test]
+fn t_roundtrip_binary_values() {
+    let mut value = parse("asset{name<s16>(logo)}").unwrap();
+    value["asset"]["data"] = Value::Bytes((0u8..=255).rev().collect());
+
+    let path = Path::new("/tmp/test_binary.io.gbln.xz");
+
+    write_io(&value, path, &GblnConfig::io_format()).unwrap();
+    let loaded = read_io(path).unwrap();
+
+    assert_eq!(loaded, value);
+
+    fs::remove_file(path).ok();
+}
+
+#[test]
+fn t_read_reports_bad_input() {
+    let value = parse("data{a<s16>(hello)}").unwrap();
+    let path = Path::new("/tmp/test_truncated.io.gbln.xz");
+
+    // A compressed file cut short fails while streaming
+    write_io(&value, path, &GblnConfig::io_format()).unwrap();
+    let bytes = fs::read(path).unwrap();
+    fs::write(path, &bytes[..bytes.len() / 2]).unwrap();
+    let err = read_io(path).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IoError);
+
+    // So does text that is not UTF-8
+    let path_text = Path::new("/tmp/test_invalid_utf8.io.gbln");
+    fs::write(path_text, b"data{a(\xFF)}").unwrap();
+    let err = read_io(path_text).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IoError);
+
+    let err = read_io(Path::new("/tmp/does_not_exist.io.gbln")).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IoError);
+
+    fs::remove_file(path).ok();
+    fs::remove_file(path_text).ok();
+}
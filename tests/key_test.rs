@@ -0,0 +1,150 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for quoted and Unicode object keys
+
+use gbln::{
+    parse, to_string, to_string_pretty, ErrorKind, Lexer, Map, PathSegment, Token, Value, ValuePath,
+};
+
+#[test]
+fn t_unicode_keys() {
+    let value = parse("café{naïve<s8>(ja) größe<u8>(3) 名前(太郎)}").unwrap();
+    assert_eq!(value["café"]["naïve"], Value::Str("ja".to_string()));
+    assert_eq!(value["café"]["größe"], Value::U8(3));
+    assert_eq!(value["café"]["名前"], Value::Str("太郎".to_string()));
+
+    // Digits and `-` may follow the first letter, as with ASCII keys
+    let value = parse("{ключ-2(1)}").unwrap();
+    assert_eq!(value["ключ-2"], Value::I64(1));
+}
+
+#[test]
+fn t_quoted_keys() {
+    let value =
+        parse(r#"user{"first name"<s32>(Ann) "v1.2"(x) ""(empty) "say \"hi\""(1)}"#).unwrap();
+    let user = &value["user"];
+    assert_eq!(user["first name"], Value::Str("Ann".to_string()));
+    assert_eq!(user["v1.2"], Value::Str("x".to_string()));
+    assert_eq!(user[""], Value::Str("empty".to_string()));
+    assert_eq!(user["say \"hi\""], Value::I64(1));
+
+    // Keys with escapes and quoted keys for nested values and arrays
+    let value = parse(r#"{"tab\there"{"a b"[1 2]}}"#).unwrap();
+    assert_eq!(value["tab\there"]["a b"][1], Value::I64(2));
+
+    // Quoted table columns
+    let value = parse(r#"rows<{"row id"<u8> name<s8>}>[(1 Ann)]"#).unwrap();
+    assert_eq!(value["rows"][0]["row id"], Value::U8(1));
+}
+
+#[test]
+fn t_invalid_quoted_keys() {
+    let err = parse("{\"first name<s8>(Ann)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnterminatedString);
+    assert_eq!((err.line, err.column), (1, 2));
+
+    // A quoted key ends on the same line
+    let err = parse("{\"first\nname\"(Ann)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnterminatedString);
+
+    let err = parse(r#"{"a\u{zz}b"(1)}"#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidEscape);
+}
+
+#[test]
+fn t_serializer_quotes_only_when_needed() {
+    let mut obj = Value::Object(Map::new());
+    obj.insert("first name", "Ann");
+    obj.insert("café", Value::I64(1));
+    obj.insert("id", Value::I64(2));
+
+    assert_eq!(
+        to_string(&obj),
+        r#"{"first name"<s4>(Ann)café<i64>(1)id<i64>(2)}"#
+    );
+}
+
+#[test]
+fn t_keys_are_bare_exactly_when_they_lex_as_one_token() {
+    for key in [
+        "a", "_x", "café", "名前", "a-b", "a_1", "3d", "-x", "-", "12.5", "-1.5", "1.2.3", "a.b",
+        "1a.5", "x y", "", ":a", "a:b", "٣", "a٣", "'q'", "1.", "1.e",
+    ] {
+        let mut lexer = Lexer::new(key);
+        let bare = matches!(lexer.next_token(), Ok(Token::Key(t)) if t == key)
+            && matches!(lexer.next_token(), Ok(Token::Eof));
+
+        let mut obj = Value::Object(Map::new());
+        obj.insert(key, Value::I64(1));
+        let text = to_string(&obj);
+        assert_eq!(!text.starts_with("{\""), bare, "{:?} -> {}", key, text);
+        assert_eq!(parse(&text).unwrap(), obj, "{}", text);
+    }
+}
+
+#[test]
+fn t_awkward_keys_round_trip() {
+    let mut inner = Map::new();
+    for key in [
+        "",
+        "a.b",
+        "x y",
+        "say \"hi\"",
+        "back\\slash",
+        "line\nbreak",
+        "3d",
+        "-x",
+    ] {
+        inner.insert(key.to_string(), Value::Str(key.to_string()));
+    }
+    let mut obj = Value::Object(Map::new());
+    obj.insert("outer key", Value::Object(inner));
+
+    for text in [to_string(&obj), to_string_pretty(&obj)] {
+        assert_eq!(parse(&text).unwrap(), obj, "{}", text);
+    }
+}
+
+#[test]
+fn t_lexer_quoted_key_token() {
+    let input = r#"{"a b"(1)}"#;
+    let tokens: Vec<Token> = Lexer::new(input).map(|t| t.unwrap().value).collect();
+    assert_eq!(tokens[1], Token::QuotedKey("\"a b\"".into()));
+
+    // The token keeps its raw text so the stream stays lossless
+    let text: String = Lexer::new(input)
+        .trivia(true)
+        .map(|t| t.unwrap().value.as_str().to_string())
+        .collect();
+    assert_eq!(text, input);
+}
+
+#[test]
+fn t_path_quoted_segments() {
+    let value = parse(r#"labels{"v1.2"{"first name"(Ann)} plain(1)}"#).unwrap();
+    assert_eq!(
+        value.get_path(r#"labels."v1.2"."first name""#).unwrap(),
+        &Value::Str("Ann".to_string())
+    );
+    assert_eq!(value.get_path("labels.plain").unwrap(), &Value::I64(1));
+
+    let err = value.get_path(r#"labels."v1.2"#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidPath);
+
+    // Display quotes keys that would not read back otherwise
+    let mut path = ValuePath::new();
+    path.push(PathSegment::Key("labels".to_string()));
+    path.push(PathSegment::Key("v1.2".to_string()));
+    path.push(PathSegment::Index(0));
+    path.push(PathSegment::Key("first name".to_string()));
+    let text = path.to_string();
+    assert_eq!(text, r#"labels."v1.2"[0].first name"#);
+
+    let segments: Vec<PathSegment> = gbln::path::parse_path(&text)
+        .unwrap()
+        .into_iter()
+        .map(|(s, _)| s)
+        .collect();
+    assert_eq!(segments, path.segments());
+}
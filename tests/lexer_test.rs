@@ -30,16 +30,13 @@ fn t_single_tokens() {
 fn t_keys() {
     let mut lexer = Lexer::new("user name age_in_years _internal");
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".into()));
     assert_eq!(
         lexer.next_token().unwrap(),
-        Token::Key("age_in_years".to_string())
-    );
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("_internal".to_string())
+        Token::Key("age_in_years".into())
     );
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("_internal".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
 }
 
@@ -47,8 +44,8 @@ fn t_keys() {
 fn t_whitespace_handling() {
     let mut lexer = Lexer::new("  user  \n\t  name  ");
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
 }
 
@@ -61,8 +58,8 @@ fn t_comments() {
 
     let mut lexer = Lexer::new(input);
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
 }
 
@@ -70,14 +67,14 @@ fn t_comments() {
 fn t_simple_object() {
     let mut lexer = Lexer::new("user{name<s32>(Alice)}");
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LBrace);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LAngle);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("s32".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("s32".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RAngle);
     assert_eq!(lexer.next_token().unwrap(), Token::LParen);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("Alice".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("Alice".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RParen);
     assert_eq!(lexer.next_token().unwrap(), Token::RBrace);
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
@@ -96,31 +93,25 @@ fn t_nested_object() {
 
     let mut lexer = Lexer::new(input);
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LBrace);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LAngle);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("s64".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("s64".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RAngle);
     assert_eq!(lexer.next_token().unwrap(), Token::LParen);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("Alice".to_string()));
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("Johnson".to_string())
-    );
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("Alice".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("Johnson".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RParen);
 
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("profile".to_string())
-    );
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("profile".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LBrace);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("age".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("age".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LAngle);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("i8".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("i8".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RAngle);
     assert_eq!(lexer.next_token().unwrap(), Token::LParen);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("25".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("25".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RParen);
     assert_eq!(lexer.next_token().unwrap(), Token::RBrace);
     assert_eq!(lexer.next_token().unwrap(), Token::RBrace);
@@ -131,20 +122,14 @@ fn t_nested_object() {
 fn t_array() {
     let mut lexer = Lexer::new("tags<s16>[rust python golang]");
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("tags".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("tags".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::LAngle);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("s16".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("s16".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RAngle);
     assert_eq!(lexer.next_token().unwrap(), Token::LBracket);
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("rust".to_string()));
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("python".to_string())
-    );
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("golang".to_string())
-    );
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("rust".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("python".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("golang".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::RBracket);
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
 }
@@ -169,7 +154,7 @@ fn t_line_column_tracking() {
 fn t_invalid_character_error() {
     let mut lexer = Lexer::new("user @ name");
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
 
     let result = lexer.next_token();
     assert!(result.is_err());
@@ -182,18 +167,9 @@ fn t_invalid_character_error() {
 fn t_underscore_keys() {
     let mut lexer = Lexer::new("_private user_id MAX_VALUE");
 
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("_private".to_string())
-    );
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("user_id".to_string())
-    );
-    assert_eq!(
-        lexer.next_token().unwrap(),
-        Token::Key("MAX_VALUE".to_string())
-    );
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("_private".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user_id".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("MAX_VALUE".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
 }
 
@@ -208,7 +184,142 @@ fn t_multiline_comments() {
 
     let mut lexer = Lexer::new(input);
 
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".to_string()));
-    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".to_string()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("user".into()));
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("name".into()));
     assert_eq!(lexer.next_token().unwrap(), Token::Eof);
 }
+
+#[test]
+fn t_parenthesized_content_is_input_slice() {
+    let input = "(Hello (nested) World) rest";
+    let mut lexer = Lexer::new(input);
+
+    assert_eq!(lexer.next_token().unwrap(), Token::LParen);
+    let content = lexer.read_parenthesized_content().unwrap();
+    assert_eq!(content, "Hello (nested) World");
+    assert_eq!(content.as_ptr(), input[1..].as_ptr());
+    assert_eq!(lexer.next_token().unwrap(), Token::Key("rest".into()));
+}
+
+/// Collect all tokens of a lossless iteration
+fn lossless(input: &str) -> Vec<gbln::Spanned<Token<'_>>> {
+    Lexer::new(input)
+        .trivia(true)
+        .content(true)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn t_spanned_positions() {
+    let tokens: Vec<_> = Lexer::new("a{\n  b(é) c(2)\n}")
+        .content(true)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let key = &tokens[2];
+    assert_eq!(key.value, Token::Key("b".into()));
+    assert_eq!((key.span.line, key.span.column), (2, 3));
+    assert_eq!(key.span.range(), 5..6);
+
+    // Columns count characters, byte offsets count bytes
+    let key = &tokens[6];
+    assert_eq!(key.value, Token::Key("c".into()));
+    assert_eq!((key.span.line, key.span.column), (2, 8));
+    assert_eq!(key.span.range(), 11..12);
+
+    let close = tokens.last().unwrap();
+    assert_eq!(close.value, Token::RBrace);
+    assert_eq!((close.span.line, close.span.column), (3, 1));
+}
+
+#[test]
+fn t_iteration_matches_next_token() {
+    let input = "user{ id(1) :| note\n tags[a b] }";
+
+    let mut lexer = Lexer::new(input);
+    let mut expected = Vec::new();
+    loop {
+        match lexer.next_token().unwrap() {
+            Token::Eof => break,
+            token => expected.push(token),
+        }
+    }
+
+    let tokens: Vec<_> = Lexer::new(input).map(|t| t.unwrap().value).collect();
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn t_content_tokens() {
+    let tokens: Vec<_> = Lexer::new("t<s8:/[<>]+/>(a (b) \\) c)e()")
+        .content(true)
+        .map(|t| t.unwrap().value)
+        .collect();
+
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Key("t".into()),
+            Token::LAngle,
+            Token::Type("s8:/[<>]+/".into()),
+            Token::RAngle,
+            Token::LParen,
+            Token::Content("a (b) \\) c".into()),
+            Token::RParen,
+            Token::Key("e".into()),
+            Token::LParen,
+            Token::RParen,
+        ]
+    );
+}
+
+#[test]
+fn t_trivia_tokens() {
+    let tokens = lossless(":| header\r\na(1)  ");
+    assert_eq!(tokens[0].value, Token::Comment(":| header\r".into()));
+    assert_eq!(tokens[1].value, Token::Whitespace("\n".into()));
+    assert!(tokens[0].value.is_trivia());
+    assert_eq!(tokens.last().unwrap().value, Token::Whitespace("  ".into()));
+}
+
+#[test]
+fn t_lossless_iteration() {
+    for input in [
+        "",
+        "  ",
+        "user{\n  id<u32>(42)\n  name<s32>(Alice Smith) :| trailing\n}\n",
+        "users<{id<u32> name<s32>};..10>[(1 Ann)\r\n(2 Bob)]",
+        "v{face<s8>(:\\)) path(C:\\\\x) emoji<s8>(😀 ✓)}",
+        "cfg{ports<u16:1..65535?>[80 null] slug<s32:/[a-z]+(-[a-z]+)*/>(a-b)}",
+        ":| only a comment",
+    ] {
+        let tokens = lossless(input);
+        let text: String = tokens.iter().map(|t| t.value.as_str()).collect();
+        assert_eq!(text, input);
+
+        // Spans are contiguous and slice out each token's text
+        let mut offset = 0;
+        for token in &tokens {
+            assert_eq!(token.span.start, offset, "{:?}", token);
+            assert_eq!(&input[token.span.range()], token.value.as_str());
+            offset = token.span.end;
+        }
+        assert_eq!(offset, input.len());
+    }
+}
+
+#[test]
+fn t_iteration_stops_after_error() {
+    let mut lexer = Lexer::new("a(1) $ b(2)");
+    assert!(lexer.by_ref().take(4).all(|t| t.is_ok()));
+
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(err.kind, gbln::ErrorKind::UnexpectedCharacter);
+    assert_eq!((err.line, err.column), (1, 6));
+    assert!(lexer.next().is_none());
+
+    // Unterminated content is reported, not silently dropped
+    let results: Vec<_> = Lexer::new("a(1").content(true).collect();
+    assert!(results.last().unwrap().is_err());
+}
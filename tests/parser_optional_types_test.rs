@@ -213,6 +213,7 @@ fn test_array_untyped_strings() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_array_untyped_mixed_types() {
     let input = "mixed[42 hello 3.14 true]";
     let result = parse(input).expect("Should parse untyped mixed array");
@@ -3,7 +3,8 @@
 
 use gbln::parser::parse;
 use gbln::value::Value;
-use std::collections::HashMap;
+use gbln::Map;
+use gbln::{Declaration, ErrorKind, TypeHint};
 
 #[test]
 fn t_single_value_i32() {
@@ -45,11 +46,11 @@ fn t_simple_object() {
     let input = "user{name<s32>(Alice)age<i8>(25)}";
     let result = parse(input).unwrap();
 
-    let mut expected_user = HashMap::new();
+    let mut expected_user = Map::new();
     expected_user.insert("name".to_string(), Value::Str("Alice".to_string()));
     expected_user.insert("age".to_string(), Value::I8(25));
 
-    let mut expected = HashMap::new();
+    let mut expected = Map::new();
     expected.insert("user".to_string(), Value::Object(expected_user));
 
     assert_eq!(result, Value::Object(expected));
@@ -60,11 +61,11 @@ fn t_simple_object_with_whitespace() {
     let input = "user{\n    name<s32>(Alice)\n    age<i8>(25)\n}";
     let result = parse(input).unwrap();
 
-    let mut expected_user = HashMap::new();
+    let mut expected_user = Map::new();
     expected_user.insert("name".to_string(), Value::Str("Alice".to_string()));
     expected_user.insert("age".to_string(), Value::I8(25));
 
-    let mut expected = HashMap::new();
+    let mut expected = Map::new();
     expected.insert("user".to_string(), Value::Object(expected_user));
 
     assert_eq!(result, Value::Object(expected));
@@ -75,14 +76,14 @@ fn t_nested_object() {
     let input = "user{profile{name<s32>(Alice)age<i8>(25)}}";
     let result = parse(input).unwrap();
 
-    let mut profile = HashMap::new();
+    let mut profile = Map::new();
     profile.insert("name".to_string(), Value::Str("Alice".to_string()));
     profile.insert("age".to_string(), Value::I8(25));
 
-    let mut user = HashMap::new();
+    let mut user = Map::new();
     user.insert("profile".to_string(), Value::Object(profile));
 
-    let mut expected = HashMap::new();
+    let mut expected = Map::new();
     expected.insert("user".to_string(), Value::Object(user));
 
     assert_eq!(result, Value::Object(expected));
@@ -99,7 +100,7 @@ fn t_typed_array() {
         Value::Str("golang".to_string()),
     ];
 
-    let mut expected = HashMap::new();
+    let mut expected = Map::new();
     expected.insert("tags".to_string(), Value::Array(tags));
 
     assert_eq!(result, Value::Object(expected));
@@ -118,7 +119,7 @@ fn t_typed_array_integers() {
         Value::I32(100),
     ];
 
-    let mut expected = HashMap::new();
+    let mut expected = Map::new();
     expected.insert("numbers".to_string(), Value::Array(numbers));
 
     assert_eq!(result, Value::Object(expected));
@@ -129,11 +130,11 @@ fn t_array_of_objects() {
     let input = "[{id<u32>(1)name<s32>(Alice)}{id<u32>(2)name<s32>(Bob)}]";
     let result = parse(input).unwrap();
 
-    let mut obj1 = HashMap::new();
+    let mut obj1 = Map::new();
     obj1.insert("id".to_string(), Value::U32(1));
     obj1.insert("name".to_string(), Value::Str("Alice".to_string()));
 
-    let mut obj2 = HashMap::new();
+    let mut obj2 = Map::new();
     obj2.insert("id".to_string(), Value::U32(2));
     obj2.insert("name".to_string(), Value::Str("Bob".to_string()));
 
@@ -184,17 +185,17 @@ fn t_complex_nested_structure() {
     "#;
     let result = parse(input).unwrap();
 
-    let mut employee1 = HashMap::new();
+    let mut employee1 = Map::new();
     employee1.insert("id".to_string(), Value::U32(1));
     employee1.insert("name".to_string(), Value::Str("Alice".to_string()));
     employee1.insert("role".to_string(), Value::Str("dev".to_string()));
 
-    let mut employee2 = HashMap::new();
+    let mut employee2 = Map::new();
     employee2.insert("id".to_string(), Value::U32(2));
     employee2.insert("name".to_string(), Value::Str("Bob".to_string()));
     employee2.insert("role".to_string(), Value::Str("pm".to_string()));
 
-    let mut company_obj = HashMap::new();
+    let mut company_obj = Map::new();
     company_obj.insert("name".to_string(), Value::Str("Acme Corp".to_string()));
     company_obj.insert(
         "employees".to_string(),
@@ -202,7 +203,7 @@ fn t_complex_nested_structure() {
     );
     company_obj.insert("active".to_string(), Value::Bool(true));
 
-    let mut expected = HashMap::new();
+    let mut expected = Map::new();
     expected.insert("company".to_string(), Value::Object(company_obj));
 
     assert_eq!(result, Value::Object(expected));
@@ -257,3 +258,123 @@ fn t_error_unexpected_token_after_value() {
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Unexpected token after value"));
 }
+
+#[test]
+fn t_object_preserves_field_order() {
+    let input = "config{name<s32>(app)version<s8>(1.0)server{port<u16>(80)}features[a b]}";
+    let result = parse(input).unwrap();
+
+    let keys: Vec<&str> = result["config"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|k| k.as_str())
+        .collect();
+    assert_eq!(keys, vec!["name", "version", "server", "features"]);
+}
+
+#[test]
+fn t_object_records_declarations() {
+    let result = parse("user{name<s32>(Alice)tags<u8>[1 2]port(8080)list[a b]inner{}}").unwrap();
+    let user = result["user"].as_object().unwrap();
+
+    assert_eq!(
+        user.declaration("name"),
+        Some(&Declaration::Typed(TypeHint::Str(32)))
+    );
+    assert_eq!(
+        user.declaration("tags"),
+        Some(&Declaration::TypedArray(TypeHint::U8))
+    );
+    assert_eq!(user.declaration("port"), Some(&Declaration::Untyped));
+    assert_eq!(user.declaration("list"), Some(&Declaration::Untyped));
+    assert_eq!(user.declaration("inner"), None);
+}
+
+#[test]
+fn t_nullable_type_hints() {
+    let result = parse("cfg{port<u32?>(8080)proxy<s64?>()timeout<u16?>(null)}").unwrap();
+
+    assert_eq!(result["cfg"]["port"], Value::U32(8080));
+    assert_eq!(result["cfg"]["proxy"], Value::Null);
+    assert_eq!(result["cfg"]["timeout"], Value::Null);
+}
+
+#[test]
+fn t_nullable_typed_array() {
+    let result = parse("readings<f64?>[1.5 null 2.5]").unwrap();
+
+    assert_eq!(
+        result["readings"],
+        Value::Array(vec![Value::F64(1.5), Value::Null, Value::F64(2.5)])
+    );
+}
+
+#[test]
+fn t_nullable_validates_non_null_values() {
+    let err = parse("cfg{port<u8?>(300)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+
+    let err = parse("cfg{port<n?>()}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidTypeHint);
+}
+
+#[test]
+fn t_ranged_type_hints() {
+    let result = parse("server{port<u16:1..65535>(8080)workers<u8:1..64>[1 8 64]}").unwrap();
+    assert_eq!(result["server"]["port"], Value::U16(8080));
+
+    let err = parse("server{port<u16:1..65535>(0)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueOutOfRange);
+    assert_eq!(err.line, 1);
+
+    let err = parse("sensor{temp<f32:-40..85>[20.5 90]}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueOutOfRange);
+
+    let err = parse("server{port<u16:9..1>(5)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidTypeHint);
+}
+
+#[test]
+fn t_enum_type_hints() {
+    let result =
+        parse("review{sentiment<e:neg|neu|pos>(pos)regions<e:eu|us|apac?>[eu null apac]}").unwrap();
+    assert_eq!(result["review"]["sentiment"].as_str(), Some("pos"));
+    assert_eq!(
+        result["review"]["regions"],
+        Value::Array(vec![
+            Value::Str("eu".to_string()),
+            Value::Null,
+            Value::Str("apac".to_string())
+        ])
+    );
+
+    let err = parse("review{sentiment<e:neg|neu|pos>(positive)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownVariant);
+    assert_eq!(err.suggestion.as_deref(), Some("did you mean 'pos'?"));
+
+    let err = parse("review{regions<e:eu|us|apac>[eu usa]}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownVariant);
+    assert_eq!(err.suggestion.as_deref(), Some("did you mean 'us'?"));
+}
+
+#[test]
+fn t_constrained_string_type_hints() {
+    let result = parse("post{slug<s32:/[a-z0-9-]+/>(hello-world) code<s4:ascii>(AB12)}").unwrap();
+    assert_eq!(result["post"]["slug"].as_str(), Some("hello-world"));
+    assert_eq!(
+        result["post"]["slug"].as_str().map(str::len),
+        Some("hello-world".len())
+    );
+
+    // Patterns may contain `<` and `>`
+    let result = parse("tag{name<s16:/<[a-z]+>/>(<b>)}").unwrap();
+    assert_eq!(result["tag"]["name"].as_str(), Some("<b>"));
+
+    let err = parse("post{slug<s32:/[a-z0-9-]+/>(Hello World)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::PatternMismatch);
+    assert_eq!(err.line, 1);
+
+    let err = parse("db{name<s4:bytes>(café)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::BytesTooLong);
+}
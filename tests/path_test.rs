@@ -0,0 +1,146 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for path-based lookup
+
+use gbln::path::parse_path;
+use gbln::{parse, ErrorKind, PathSegment, Value};
+
+const INPUT: &str = "app{server{port<u16>(8080)}users[{name<s8>(Ann)}{name<s8>(Bob)}]}";
+
+#[test]
+fn t_parse_path_segments() {
+    let segments: Vec<PathSegment> = parse_path("users[3].name")
+        .unwrap()
+        .into_iter()
+        .map(|(s, _)| s)
+        .collect();
+
+    assert_eq!(
+        segments,
+        vec![
+            PathSegment::Key("users".to_string()),
+            PathSegment::Index(3),
+            PathSegment::Key("name".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn t_parse_path_invalid() {
+    for path in ["a..b", "a.", ".a", "a[x]", "a[1", "a[1]b"] {
+        let err = parse_path(path).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidPath, "path {:?}", path);
+    }
+}
+
+#[test]
+fn t_get_path_nested_and_array() {
+    let value = parse(INPUT).unwrap();
+
+    assert_eq!(
+        value.get_path("app.server.port").unwrap(),
+        &Value::U16(8080)
+    );
+    assert_eq!(
+        value.get_path("app.users[1].name").unwrap().as_str(),
+        Some("Bob")
+    );
+    assert_eq!(value.get_path("").unwrap(), &value);
+}
+
+#[test]
+fn t_get_path_missing_key_names_segment() {
+    let value = parse(INPUT).unwrap();
+    let err = value.get_path("app.server.host").unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::PathNotFound);
+    assert!(err.message.contains("key 'host'"));
+    assert!(err.message.contains("'app.server'"));
+    assert_eq!(err.column, 12);
+    assert_eq!(err.suggestion.as_deref(), Some("available keys: port"));
+}
+
+#[test]
+fn t_path_columns_count_characters() {
+    let value = parse("café{größe<u8>(3)}").unwrap();
+
+    let err = value.get_path("café.größe.x").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+    assert_eq!(err.column, 12);
+
+    let err = value.get_path(r#"café."größe"[0]"#).unwrap_err();
+    assert_eq!(err.column, 13);
+
+    let err = value.get_path("café.").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidPath);
+    assert_eq!(err.column, 6);
+}
+
+#[test]
+fn t_get_path_index_out_of_bounds() {
+    let value = parse(INPUT).unwrap();
+    let err = value.get_path("app.users[5].name").unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::PathNotFound);
+    assert!(err.message.contains("index 5"));
+    assert!(err.message.contains("length 2"));
+}
+
+#[test]
+fn t_get_path_wrong_type_names_segment() {
+    let value = parse(INPUT).unwrap();
+
+    let err = value.get_path("app.server[0]").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+    assert!(err.message.contains("'app.server' is object not array"));
+
+    let err = value.get_path("app.server.port.value").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+    assert!(err.message.contains("'app.server.port' is u16 not object"));
+}
+
+#[test]
+fn t_get_path_mut_edits() {
+    let mut value = parse(INPUT).unwrap();
+    *value.get_path_mut("app.users[0].name").unwrap() = Value::Str("Eve".to_string());
+
+    assert_eq!(value["app"]["users"][0]["name"].as_str(), Some("Eve"));
+}
+
+#[test]
+fn t_set_path_replaces_and_inserts() {
+    let mut value = parse(INPUT).unwrap();
+
+    let old = value.set_path("app.server.port", Value::U16(443)).unwrap();
+    assert_eq!(old, Some(Value::U16(8080)));
+
+    let old = value
+        .set_path("app.server.host", Value::Str("localhost".to_string()))
+        .unwrap();
+    assert_eq!(old, None);
+    assert_eq!(value["app"]["server"]["host"].as_str(), Some("localhost"));
+
+    let old = value.set_path("app.users[1]", Value::Null).unwrap();
+    assert!(old.unwrap().is_object());
+}
+
+#[test]
+fn t_set_path_requires_intermediates() {
+    let mut value = parse(INPUT).unwrap();
+
+    let err = value.set_path("app.db.host", Value::Null).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::PathNotFound);
+    assert!(err.message.contains("key 'db'"));
+
+    let err = value.set_path("app.users[2]", Value::Null).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::PathNotFound);
+}
+
+#[test]
+fn t_pointer() {
+    let value = parse(INPUT).unwrap();
+
+    assert_eq!(value.pointer("app.server.port"), Some(&Value::U16(8080)));
+    assert_eq!(value.pointer("app.missing"), None);
+}
@@ -0,0 +1,122 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for parsing streamed input
+
+use gbln::{
+    parse, parse_reader, parse_reader_with_options, ErrorKind, Lexer, ParseOptions, Spanned, Token,
+};
+use std::io::{self, BufReader, Read};
+
+const INPUT: &str = "app{
+    :| Server settings
+    \"display name\"<s32>(Café (main) \\u{1F600})
+    ports<u16;..4>[80 443]
+    users<{id<u32> name<s16>}>[(1 Zoë)(2 名前)]
+    ratio<f64>(0.75)
+}";
+
+/// Reader that returns its input a few bytes at a time
+fn chunked(input: &[u8], capacity: usize) -> BufReader<&[u8]> {
+    BufReader::with_capacity(capacity, input)
+}
+
+/// Reader that fails after its input
+struct Failing<'a>(&'a [u8]);
+
+impl Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(io::Error::other("disk on fire"));
+        }
+        self.0.read(buf)
+    }
+}
+
+#[test]
+fn t_parse_reader_matches_parse() {
+    let expected = parse(INPUT).unwrap();
+
+    // Small buffers split tokens and multi-byte characters across reads
+    for capacity in [1, 2, 3, 5, 8192] {
+        let value = parse_reader(chunked(INPUT.as_bytes(), capacity)).unwrap();
+        assert_eq!(value, expected, "capacity {}", capacity);
+    }
+}
+
+#[test]
+fn t_parse_reader_long_input() {
+    // Enough text for the lexer to release what it has read
+    let long = "é".repeat(5000);
+    let mut input = String::from("{");
+    for i in 0..200 {
+        input.push_str(&format!("k{}<s8>(v{}) ", i, i));
+    }
+    input.push_str(&format!("long({}) n<u8>(7)}}", long));
+
+    let value = parse_reader(chunked(input.as_bytes(), 7)).unwrap();
+    assert_eq!(value, parse(&input).unwrap());
+    assert_eq!(value["long"].as_str(), Some(long.as_str()));
+}
+
+#[test]
+fn t_parse_reader_errors_match_parse() {
+    for input in [
+        "a{b<u8>(300)}",
+        "a{\n  b(1)\n  c<s2>(abc)}",
+        "a{b(1)",
+        "a{\"b(1)}",
+    ] {
+        let expected = parse(input).unwrap_err();
+        let err = parse_reader(chunked(input.as_bytes(), 2)).unwrap_err();
+        assert_eq!(err.kind, expected.kind, "{}", input);
+        assert_eq!((err.line, err.column), (expected.line, expected.column));
+    }
+
+    let options = ParseOptions::strict();
+    let err = parse_reader_with_options("v<b>(yes)".as_bytes(), &options).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+}
+
+#[test]
+fn t_parse_reader_invalid_utf8() {
+    let mut input = b"a{\n  b(ok)\n  c(x".to_vec();
+    input.extend_from_slice(&[0xFF, b')', b'}']);
+
+    let err = parse_reader(chunked(&input, 4)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IoError);
+    assert!(err.message.contains("not valid UTF-8"), "{}", err.message);
+    assert_eq!((err.line, err.column), (3, 6));
+
+    // A character cut off at the end of the input
+    let err = parse_reader(&b"a(\xC3"[..]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IoError);
+}
+
+#[test]
+fn t_parse_reader_read_error() {
+    let reader = BufReader::new(Failing(b"a{b(1)"));
+    let err = parse_reader(reader).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IoError);
+    assert!(err.message.contains("disk on fire"), "{}", err.message);
+}
+
+/// All tokens of a lossless token stream
+fn tokens(lexer: Lexer<'_>) -> Vec<Spanned<Token<'_>>> {
+    lexer
+        .trivia(true)
+        .content(true)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn t_lexer_from_reader() {
+    let from_str = tokens(Lexer::new(INPUT));
+    let streamed = tokens(Lexer::from_reader(chunked(INPUT.as_bytes(), 3)));
+
+    // Same tokens and spans, so the stream is lossless as well
+    assert_eq!(streamed, from_str);
+    let text: String = streamed.iter().map(|t| t.value.as_str()).collect();
+    assert_eq!(text, INPUT);
+}
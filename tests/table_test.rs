@@ -0,0 +1,188 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for tabular arrays of records
+
+use gbln::{
+    parse, to_string, to_string_pretty, to_string_with_config, Declaration, ErrorKind, GblnConfig,
+    Map, TypeHint, Value,
+};
+
+/// Build a record from field names and values
+fn record(fields: Vec<(&str, Value)>) -> Value {
+    let mut map = Map::new();
+    for (key, value) in fields {
+        map.insert(key.to_string(), value);
+    }
+    Value::Object(map)
+}
+
+#[test]
+fn t_parse_table() {
+    let value = parse("users<{id<u32> name<s32> admin<b?>}>[(1 Ann t) (2 Bob null)]").unwrap();
+    let users = value["users"].as_array().unwrap();
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0]["id"], Value::U32(1));
+    assert_eq!(users[0]["name"], Value::Str("Ann".to_string()));
+    assert_eq!(users[0]["admin"], Value::Bool(true));
+    assert_eq!(users[1]["id"], Value::U32(2));
+    assert!(users[1]["admin"].is_null());
+
+    // Rows are ordinary objects with their columns in schema order
+    let keys: Vec<_> = users[1].as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["id", "name", "admin"]);
+
+    match value.as_object().unwrap().declaration("users") {
+        Some(Declaration::Table(columns)) => {
+            assert_eq!(columns[0], ("id".to_string(), TypeHint::U32));
+            assert_eq!(columns[1], ("name".to_string(), TypeHint::Str(32)));
+        }
+        other => panic!("expected table declaration, got {:?}", other),
+    }
+
+    let value = parse("db{users<{id<u8>}>[]}").unwrap();
+    assert_eq!(value["db"]["users"], Value::Array(vec![]));
+}
+
+#[test]
+fn t_table_cells_are_validated() {
+    let err = parse("users<{id<u8> name<s4>}>[(1 Ann)\n(300 Bob)]").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+    assert_eq!(err.line, 2);
+
+    let err = parse("users<{id<u8> name<s4>}>[(1 Annabelle)]").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::StringTooLong);
+}
+
+#[test]
+fn t_table_rows_need_every_column() {
+    let err = parse("users<{id<u8> name<s4>}>[(1 Ann)(2)]").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ColumnCountMismatch);
+    assert!(
+        err.message
+            .contains("row 2 has 1 values, expected 2 (id name)"),
+        "{}",
+        err.message
+    );
+
+    let err = parse("users<{id<u8> name<s4>}>[(1 Ann x)]").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ColumnCountMismatch);
+}
+
+#[test]
+fn t_invalid_table_schemas() {
+    for input in [
+        "t<{}>[]",
+        "t<{id}>[]",
+        "t<{id<u8> id<u16>}>[]",
+        "t<{id<x64>}>[]",
+        "t<{id<u8>} x>[]",
+        "t<{id<{a<u8>}>}>[]",
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidTypeHint, "{}", input);
+        assert!(
+            err.message.starts_with("Invalid table schema"),
+            "{}",
+            err.message
+        );
+    }
+
+    // Schemas only describe keyed arrays
+    assert!(parse("t<{id<u8>}>(1)").is_err());
+    assert!(parse("t[<{id<u8>}>(1)]").is_err());
+}
+
+#[test]
+fn t_serialize_records_as_table() {
+    let mut obj = Value::Object(Map::new());
+    obj.insert(
+        "users",
+        vec![
+            record(vec![("id", Value::I64(1)), ("name", "Ann".into())]),
+            record(vec![("id", Value::I64(2)), ("name", "Bob".into())]),
+        ],
+    );
+
+    let text = to_string(&obj);
+    assert_eq!(text, "{users<{id<i64>name<s4>}>[(1 Ann)(2 Bob)]}");
+    assert_eq!(parse(&text).unwrap(), obj);
+}
+
+#[test]
+fn t_table_keeps_declared_schema() {
+    let input = "{users<{id<u32>name<s32>}>[(1 Ann)(2 Bob)]}";
+    let value = parse(input).unwrap();
+    assert_eq!(to_string(&value), input);
+
+    // Cells written as ordinary objects keep their declared hints too
+    let value = parse("{users[{id<u32>(1)name<s32>(Ann)}{id<u32>(2)name<s32>(Bob)}]}").unwrap();
+    assert_eq!(to_string(&value), input);
+
+    // An empty table keeps its schema
+    let input = "{users<{id<u32>}>[]}";
+    assert_eq!(to_string(&parse(input).unwrap()), input);
+
+    let config = GblnConfig::new().reinfer_types(true);
+    let value = parse("{users<{id<u32>name<s32>}>[(1 Ann)]}").unwrap();
+    assert_eq!(
+        to_string_with_config(&value, &config).unwrap(),
+        "{users<{id<u32>name<s4>}>[(1 Ann)]}"
+    );
+}
+
+#[test]
+fn t_table_infers_nullable_columns() {
+    let mut obj = Value::Object(Map::new());
+    obj.insert(
+        "rows",
+        vec![
+            record(vec![("score", Value::F64(1.5)), ("note", Value::Null)]),
+            record(vec![("score", Value::Null), ("note", Value::Null)]),
+        ],
+    );
+
+    let text = to_string(&obj);
+    assert_eq!(text, "{rows<{score<f64?>note<n>}>[(1.5 null)(null null)]}");
+    assert_eq!(parse(&text).unwrap(), obj);
+}
+
+#[test]
+fn t_irregular_records_are_not_tables() {
+    let cases = [
+        // Different fields
+        vec![
+            record(vec![("a", Value::I64(1))]),
+            record(vec![("b", Value::I64(2))]),
+        ],
+        // Different types in one column
+        vec![
+            record(vec![("a", Value::I64(1))]),
+            record(vec![("a", "x".into())]),
+        ],
+        // Nested values
+        vec![record(vec![("a", Value::Array(vec![]))])],
+        // Cells that are not bare tokens
+        vec![record(vec![("a", "two words".into())])],
+    ];
+
+    for rows in cases {
+        let mut obj = Value::Object(Map::new());
+        obj.insert("rows", rows);
+        let text = to_string(&obj);
+        assert!(text.starts_with("{rows[{"), "{}", text);
+        assert_eq!(parse(&text).unwrap(), obj);
+    }
+}
+
+#[test]
+fn t_pretty_table() {
+    let value = parse("db{users<{id<u32> name<s32>}>[(1 Ann)(2 Bob)]}").unwrap();
+    let text = to_string_pretty(&value);
+    assert_eq!(
+        text,
+        "{\n  db{\n    users<{id<u32> name<s32>}>[\n      (1 Ann)\n      (2 Bob)\n    ]\n  }\n}"
+    );
+    assert_eq!(parse(&text).unwrap(), value);
+}
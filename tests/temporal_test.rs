@@ -0,0 +1,155 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for date, time and timestamp types
+
+use gbln::{parse, to_string, Date, DateTime, ErrorKind, Time, Timestamp, TypeHint, Value};
+
+#[test]
+fn t_parse_date() {
+    let date: Date = "2024-02-29".parse().unwrap();
+    assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+    assert_eq!(date.to_string(), "2024-02-29");
+
+    for invalid in [
+        "2023-02-29",
+        "2024-13-01",
+        "2024-04-31",
+        "2024-1-01",
+        "24-01-01",
+        "2024/01/01",
+    ] {
+        assert!(invalid.parse::<Date>().is_err(), "date {:?}", invalid);
+    }
+}
+
+#[test]
+fn t_parse_time() {
+    let time: Time = "14:30:05.250".parse().unwrap();
+    assert_eq!(
+        (time.hour(), time.minute(), time.second(), time.nanosecond()),
+        (14, 30, 5, 250_000_000)
+    );
+    assert_eq!(time.to_string(), "14:30:05.25");
+    assert_eq!("00:00:00".parse::<Time>().unwrap().to_string(), "00:00:00");
+
+    for invalid in [
+        "24:00:00",
+        "12:60:00",
+        "12:00",
+        "12:00:00.",
+        "12:00:00.1234567890",
+    ] {
+        assert!(invalid.parse::<Time>().is_err(), "time {:?}", invalid);
+    }
+}
+
+#[test]
+fn t_parse_datetime() {
+    let dt: DateTime = "2024-03-15T14:30:00+02:00".parse().unwrap();
+    assert_eq!(dt.date(), Date::new(2024, 3, 15).unwrap());
+    assert_eq!(dt.time(), Time::new(14, 30, 0, 0).unwrap());
+    assert_eq!(dt.offset_minutes(), 120);
+    assert_eq!(dt.to_string(), "2024-03-15T14:30:00+02:00");
+
+    // Canonical form uses upper-case T and Z
+    let utc: DateTime = "2024-03-15t14:30:00.5z".parse().unwrap();
+    assert_eq!(utc.to_string(), "2024-03-15T14:30:00.5Z");
+
+    for invalid in [
+        "2024-03-15 14:30:00Z",
+        "2024-03-15T14:30:00",
+        "2024-03-15T14:30:00+2:00",
+        "2024-03-15T14:30:00+24:00",
+    ] {
+        assert!(invalid.parse::<DateTime>().is_err(), "dt {:?}", invalid);
+    }
+}
+
+#[test]
+fn t_parse_timestamp() {
+    let secs: Timestamp = "1710513000".parse().unwrap();
+    assert_eq!(secs.unix_seconds(), 1_710_513_000);
+    assert_eq!(secs.to_string(), "1710513000");
+
+    let millis: Timestamp = "1710513000250ms".parse().unwrap();
+    assert_eq!(millis.unix_millis(), 1_710_513_000_250);
+    assert_eq!(millis.unix_seconds(), 1_710_513_000);
+    assert_eq!(millis.subsec_millis(), 250);
+    assert_eq!(millis.to_string(), "1710513000250ms");
+
+    // Whole seconds given in milliseconds are displayed as seconds
+    assert_eq!("5000ms".parse::<Timestamp>().unwrap().to_string(), "5");
+
+    let before_epoch: Timestamp = "-1500ms".parse().unwrap();
+    assert_eq!(before_epoch.unix_seconds(), -2);
+    assert_eq!(before_epoch.subsec_millis(), 500);
+
+    for invalid in ["", "ms", "12.5", "1e9", "+5", "9223372036854775807"] {
+        assert!(invalid.parse::<Timestamp>().is_err(), "ts {:?}", invalid);
+    }
+}
+
+#[test]
+fn t_temporal_type_hints() {
+    assert_eq!(TypeHint::from_str("date").unwrap(), TypeHint::Date);
+    assert_eq!(TypeHint::from_str("time").unwrap(), TypeHint::Time);
+    assert_eq!(TypeHint::from_str("dt").unwrap(), TypeHint::DateTime);
+    assert_eq!(TypeHint::from_str("ts").unwrap(), TypeHint::Timestamp);
+    assert_eq!(TypeHint::DateTime.as_str(), "dt");
+}
+
+#[test]
+fn t_parse_temporal_fields() {
+    let value = parse(
+        "event{day<date>(2024-03-15)at<time>(09:00:00)start<dt>(2024-03-15T09:00:00Z)seen<ts>(1710493200)}",
+    )
+    .unwrap();
+    let event = &value["event"];
+
+    assert_eq!(
+        event["day"].as_date(),
+        Some(Date::new(2024, 3, 15).unwrap())
+    );
+    assert_eq!(event["at"].as_time().unwrap().hour(), 9);
+    assert_eq!(event["start"].as_datetime().unwrap().offset_minutes(), 0);
+    assert_eq!(
+        event["seen"].as_timestamp().unwrap().unix_seconds(),
+        1_710_493_200
+    );
+    assert_eq!(event["day"].type_name(), "date");
+}
+
+#[test]
+fn t_parse_temporal_invalid() {
+    let err = parse("event{day<date>(2024-02-30)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+    assert!(err.message.contains("day 30 out of range"));
+
+    let err = parse("event{start<dt>(2024-03-15T09:00:00)}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+}
+
+#[test]
+fn t_parse_date_typed_array() {
+    let value = parse("holidays<date>[2024-12-25 2024-12-26]").unwrap();
+    assert_eq!(
+        value["holidays"][1],
+        Value::Date(Date::new(2024, 12, 26).unwrap())
+    );
+}
+
+#[test]
+fn t_serialize_temporal_canonical() {
+    let value =
+        parse("event{start<dt>(2024-03-15t09:00:00.500z)seen<ts>(1710493200000ms)}").unwrap();
+
+    assert_eq!(
+        to_string(&value),
+        "{event{start<dt>(2024-03-15T09:00:00.5Z)seen<ts>(1710493200)}}"
+    );
+    assert_eq!(
+        to_string(&Value::from(Date::new(2024, 1, 2).unwrap())),
+        "<date>(2024-01-02)"
+    );
+}
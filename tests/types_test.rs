@@ -3,7 +3,7 @@
 
 //! Integration tests for GBLN Type System
 
-use gbln::TypeHint;
+use gbln::{ErrorKind, NumRange, Pattern, StrConstraint, TypeHint, Value};
 
 #[test]
 fn t_parse_integer_types() {
@@ -84,3 +84,320 @@ fn t_roundtrip_conversion() {
         assert_eq!(hint.as_str(), type_str);
     }
 }
+
+#[test]
+fn t_integer_range_bounds() {
+    assert_eq!(TypeHint::U8.integer_range(), Some((0, 255)));
+    assert_eq!(TypeHint::I16.integer_range(), Some((-32768, 32767)));
+    assert_eq!(TypeHint::F32.integer_range(), None);
+
+    assert!(TypeHint::U8.check_integer(255).is_ok());
+    assert!(TypeHint::U8.check_integer(256).is_err());
+    assert!(TypeHint::U64.check_integer(-1).is_err());
+    assert!(TypeHint::Bool.check_integer(0).is_err());
+}
+
+#[test]
+fn t_parse_value_integer_out_of_range() {
+    let err = TypeHint::I8.parse_value("200").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+    assert!(err.message.contains("out of range for i8"));
+
+    let err = TypeHint::U8.parse_value("abc").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+    assert!(err.message.contains("Cannot parse 'abc' as u8"));
+}
+
+#[test]
+fn t_parse_nullable_types() {
+    assert_eq!(
+        TypeHint::from_str("u32?").unwrap(),
+        TypeHint::Nullable(Box::new(TypeHint::U32))
+    );
+    assert_eq!(
+        TypeHint::from_str("s64?").unwrap(),
+        TypeHint::Nullable(Box::new(TypeHint::Str(64)))
+    );
+    assert_eq!(TypeHint::from_str("f64?").unwrap().as_str(), "f64?");
+
+    assert!(TypeHint::from_str("n?").is_err());
+    assert!(TypeHint::from_str("u32??").is_err());
+    assert!(TypeHint::from_str("?").is_err());
+}
+
+#[test]
+fn t_nullable_parse_value() {
+    let hint = TypeHint::from_str("u8?").unwrap();
+
+    assert!(hint.is_nullable());
+    assert_eq!(hint.non_null(), &TypeHint::U8);
+    assert_eq!(hint.parse_value("").unwrap(), Value::Null);
+    assert_eq!(hint.parse_value("null").unwrap(), Value::Null);
+    assert_eq!(hint.parse_value("42").unwrap(), Value::U8(42));
+
+    let err = hint.parse_value("300").unwrap_err();
+    assert!(err.message.contains("out of range for u8"));
+}
+
+#[test]
+fn t_parse_ranged_types() {
+    assert_eq!(
+        TypeHint::from_str("u8:1..64").unwrap(),
+        TypeHint::Ranged(
+            Box::new(TypeHint::U8),
+            NumRange::Integer {
+                min: Some(1),
+                max: Some(64)
+            }
+        )
+    );
+    assert_eq!(
+        TypeHint::from_str("f32:-40..85").unwrap(),
+        TypeHint::Ranged(
+            Box::new(TypeHint::F32),
+            NumRange::Float {
+                min: Some(-40.0),
+                max: Some(85.0)
+            }
+        )
+    );
+
+    for hint in [
+        "u8:1..64",
+        "f32:-40..85",
+        "i32:..0",
+        "u16:1..",
+        "f64:0.5..1.5",
+        "u8:1..64?",
+    ] {
+        assert_eq!(TypeHint::from_str(hint).unwrap().as_str(), hint);
+    }
+}
+
+#[test]
+fn t_invalid_ranged_types() {
+    for hint in [
+        "u8:1..300",
+        "u8:64..1",
+        "u8:1.5..2",
+        "f32:nan..1",
+        "u8:..",
+        "u8:1-64",
+        "s32:1..4",
+        "b:0..1",
+    ] {
+        assert!(TypeHint::from_str(hint).is_err(), "hint {:?}", hint);
+    }
+}
+
+#[test]
+fn t_ranged_parse_value() {
+    let workers = TypeHint::from_str("u8:1..64").unwrap();
+    assert_eq!(workers.parse_value("64").unwrap(), Value::U8(64));
+
+    let err = workers.parse_value("0").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueOutOfRange);
+    assert!(err.message.contains("out of range for u8:1..64 (1 to 64)"));
+
+    // The width of the base type is still checked first
+    let err = workers.parse_value("300").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+
+    let temp = TypeHint::from_str("f32:-40..85").unwrap();
+    assert_eq!(temp.parse_value("-40").unwrap(), Value::F32(-40.0));
+    assert_eq!(
+        temp.parse_value("85.5").unwrap_err().kind,
+        ErrorKind::ValueOutOfRange
+    );
+}
+
+#[test]
+fn t_f32_range_compares_at_f32_width() {
+    // 0.1f32 is slightly above 0.1f64, but the same as the bound as an f32
+    let value = gbln::parse("x<f32:0..0.1>(0.1)").unwrap();
+    assert_eq!(value["x"], Value::F32(0.1));
+
+    let hint = TypeHint::from_str("f32:0.1..0.2").unwrap();
+    assert_eq!(hint.parse_value("0.1").unwrap(), Value::F32(0.1));
+    assert_eq!(hint.parse_value("0.2").unwrap(), Value::F32(0.2));
+    assert_eq!(
+        hint.parse_value("0.21").unwrap_err().kind,
+        ErrorKind::ValueOutOfRange
+    );
+}
+
+#[test]
+fn t_u128_takes_no_range() {
+    let err = TypeHint::from_str("u128:1..10").unwrap_err();
+    assert!(
+        err.contains("u128 does not take a range (use u64 or i128 instead)"),
+        "{}",
+        err
+    );
+    let err = gbln::parse("x<u128:1..>(5)").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidTypeHint);
+}
+
+#[test]
+fn t_parse_enum_type() {
+    let hint = TypeHint::from_str("e:low|medium|high").unwrap();
+    assert_eq!(
+        hint,
+        TypeHint::Enum(vec![
+            "low".to_string(),
+            "medium".to_string(),
+            "high".to_string()
+        ])
+    );
+    assert_eq!(hint.as_str(), "e:low|medium|high");
+
+    assert!(TypeHint::from_str("e:").is_err());
+    assert!(TypeHint::from_str("e:low||high").is_err());
+    assert!(TypeHint::from_str("e:low|low").is_err());
+}
+
+#[test]
+fn t_enum_parse_value_suggests_closest_variant() {
+    let hint = TypeHint::from_str("e:debug|info|warn|error").unwrap();
+    assert_eq!(
+        hint.parse_value("warn").unwrap(),
+        Value::Str("warn".to_string())
+    );
+
+    let err = hint.parse_value("eror").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownVariant);
+    assert!(err
+        .message
+        .contains("'eror' is not one of debug|info|warn|error"));
+    assert_eq!(err.suggestion.as_deref(), Some("did you mean 'error'?"));
+}
+
+#[test]
+fn t_parse_constrained_string_types() {
+    assert_eq!(
+        TypeHint::from_str("s32:ascii").unwrap(),
+        TypeHint::ConstrainedStr(32, StrConstraint::Ascii)
+    );
+    assert_eq!(
+        TypeHint::from_str("s32:bytes").unwrap(),
+        TypeHint::ConstrainedStr(32, StrConstraint::Bytes)
+    );
+
+    let slug = TypeHint::from_str("s64:/[a-z0-9-]+/").unwrap();
+    assert_eq!(
+        slug,
+        TypeHint::ConstrainedStr(64, StrConstraint::Pattern("[a-z0-9-]+".parse().unwrap()))
+    );
+    assert_eq!(slug.as_str(), "s64:/[a-z0-9-]+/");
+    assert_eq!(
+        TypeHint::from_str("s8:ascii?").unwrap().as_str(),
+        "s8:ascii?"
+    );
+
+    for hint in [
+        "s32:utf8",
+        "s32:/abc",
+        "s32:/(a/",
+        "s32:/[z-a]/",
+        "s32:/a{3,1}/",
+    ] {
+        assert!(TypeHint::from_str(hint).is_err(), "hint {:?}", hint);
+    }
+}
+
+#[test]
+fn t_constrained_string_parse_value() {
+    let ascii = TypeHint::from_str("s8:ascii").unwrap();
+    assert!(ascii.parse_value("hello").is_ok());
+    assert_eq!(
+        ascii.parse_value("héllo").unwrap_err().kind,
+        ErrorKind::TypeMismatch
+    );
+    assert_eq!(
+        ascii.parse_value("too long!").unwrap_err().kind,
+        ErrorKind::StringTooLong
+    );
+
+    // `bytes` bounds the UTF-8 length: 4 characters but 8 bytes
+    let bytes = TypeHint::from_str("s6:bytes").unwrap();
+    assert!(bytes.parse_value("abcdef").is_ok());
+    let err = bytes.parse_value("äöüß").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::BytesTooLong);
+    assert!(err.message.contains("8 bytes (max 6)"));
+
+    let slug = TypeHint::from_str("s16:/[a-z0-9-]+/").unwrap();
+    assert!(slug.parse_value("my-post-2").is_ok());
+    let err = slug.parse_value("My Post").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::PatternMismatch);
+    assert!(err.message.contains("does not match /[a-z0-9-]+/"));
+    assert_eq!(
+        slug.parse_value("a-very-long-slug-indeed")
+            .unwrap_err()
+            .kind,
+        ErrorKind::StringTooLong
+    );
+}
+
+#[test]
+fn t_pattern_matching() {
+    let cases: &[(&str, &[&str], &[&str])] = &[
+        ("abc", &["abc"], &["ab", "abcd", "xabc"]),
+        ("a.c", &["abc", "a-c"], &["ac"]),
+        ("[^,]*", &["", "a b"], &["a,b"]),
+        (r"\d{3}-\d{4}", &["555-1234"], &["5551234", "55-1234"]),
+        (r"\w+@\w+\.com", &["ann@example.com"], &["ann@example.org"]),
+        ("(ab|cd)+", &["ab", "abcdab"], &["", "abc"]),
+        ("colou?r", &["color", "colour"], &["colouur"]),
+        ("a{2,}b?", &["aa", "aaaab"], &["a", "ab"]),
+        (r"x\/y", &["x/y"], &["xy"]),
+        ("^a|b$", &["a", "b"], &["ab"]),
+    ];
+
+    for (source, matching, failing) in cases {
+        let pattern: Pattern = source.parse().unwrap();
+        for s in *matching {
+            assert!(pattern.is_match(s), "/{}/ should match {:?}", source, s);
+        }
+        for s in *failing {
+            assert!(
+                !pattern.is_match(s),
+                "/{}/ should not match {:?}",
+                source,
+                s
+            );
+        }
+    }
+
+    // Nested quantifiers stay fast
+    let pattern: Pattern = "(a*)*b".parse().unwrap();
+    assert!(!pattern.is_match(&"a".repeat(10_000)));
+}
+
+#[test]
+fn t_pattern_size_is_bounded() {
+    // Repeats of nothing collapse instead of looping at compile time
+    let pattern: Pattern = "((){1000}){1000}x".parse().unwrap();
+    assert!(pattern.is_match("x"));
+    assert!("(()*)+".parse::<Pattern>().unwrap().is_match(""));
+
+    // Counts above the limit are rejected up front
+    for source in [
+        "(){400000000}",
+        "((){60000}){60000}",
+        "a{1001}",
+        "a{2,5000}",
+    ] {
+        let err = source.parse::<Pattern>().unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidTypeHint, "{}", source);
+    }
+    let err = gbln::parse("a<s8:/((){60000}){60000}/>(x)").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidTypeHint);
+
+    // Work is counted even when repeats emit few instructions
+    for source in ["(^){1000}", "((a|b){1000}){1000}", "(()()){1000}"] {
+        let start = std::time::Instant::now();
+        let _ = source.parse::<Pattern>();
+        assert!(start.elapsed().as_secs() < 1, "{}", source);
+    }
+    assert!("((a|b){1000}){1000}".parse::<Pattern>().is_err());
+}
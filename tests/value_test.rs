@@ -0,0 +1,383 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for the Value API
+
+use gbln::{parse, ErrorKind, Map, Value};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn t_is_exact_variant() {
+    assert!(Value::I16(1).is_i16());
+    assert!(!Value::I16(1).is_i32());
+    assert!(Value::U64(1).is_u64());
+    assert!(Value::F32(1.0).is_f32());
+    assert!(!Value::F32(1.0).is_f64());
+    assert!(Value::Null.is_null());
+}
+
+#[test]
+fn t_is_category() {
+    assert!(Value::U8(1).is_integer());
+    assert!(Value::I64(-1).is_integer());
+    assert!(!Value::F64(1.0).is_integer());
+    assert!(Value::F32(1.0).is_float());
+    assert!(Value::I8(1).is_number());
+    assert!(Value::F64(1.0).is_number());
+    assert!(!Value::Str("1".to_string()).is_number());
+}
+
+#[test]
+fn t_integer_accessors_widen() {
+    let v = Value::U8(200);
+    assert_eq!(v.as_u8(), Some(200));
+    assert_eq!(v.as_u16(), Some(200));
+    assert_eq!(v.as_u64(), Some(200));
+    assert_eq!(v.as_i16(), Some(200));
+    assert_eq!(v.as_i64(), Some(200));
+}
+
+#[test]
+fn t_integer_accessors_narrow_when_value_fits() {
+    let v = Value::I64(4);
+    assert_eq!(v.as_u8(), Some(4));
+    assert_eq!(v.as_i8(), Some(4));
+    assert_eq!(v.as_u32(), Some(4));
+}
+
+#[test]
+fn t_integer_accessors_reject_lossy() {
+    assert_eq!(Value::U16(300).as_u8(), None);
+    assert_eq!(Value::I8(-1).as_u64(), None);
+    assert_eq!(Value::U64(u64::MAX).as_i64(), None);
+    assert_eq!(Value::F64(1.0).as_i64(), None);
+    assert_eq!(Value::Str("1".to_string()).as_u8(), None);
+}
+
+#[test]
+fn t_float_accessors() {
+    assert_eq!(Value::F32(1.5).as_f32(), Some(1.5));
+    assert_eq!(Value::F32(1.5).as_f64(), Some(1.5));
+    assert_eq!(Value::F64(2.5).as_f64(), Some(2.5));
+    assert_eq!(Value::F64(2.5).as_f32(), None);
+    assert_eq!(Value::I32(1).as_f64(), None);
+}
+
+#[test]
+fn t_accessors_survive_type_hint_change() {
+    let narrow = parse("server{port<u16>(8080)workers<u8>(4)}").unwrap();
+    let wide = parse("server{port<u32>(8080)workers<u16>(4)}").unwrap();
+    let untyped = parse("server{port(8080)workers(4)}").unwrap();
+
+    for value in [&narrow, &wide, &untyped] {
+        assert_eq!(value["server"]["port"].as_u16(), Some(8080));
+        assert_eq!(value["server"]["workers"].as_u8(), Some(4));
+    }
+}
+
+#[test]
+fn t_other_accessors() {
+    assert_eq!(Value::Str("hi".to_string()).as_str(), Some("hi"));
+    assert_eq!(Value::Bool(true).as_bool(), Some(true));
+    assert_eq!(Value::Null.as_null(), Some(()));
+    assert_eq!(Value::Bool(false).as_null(), None);
+}
+
+#[test]
+fn t_get_object_field_and_array_element() {
+    let value = parse("users[{name<s8>(Ann)}{name<s8>(Bob)}]").unwrap();
+
+    assert_eq!(
+        value.get("users").and_then(|u| u.get(1)),
+        Some(&value["users"][1])
+    );
+    assert_eq!(value["users"][1]["name"].as_str(), Some("Bob"));
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(value["users"].get(5), None);
+    assert_eq!(value["users"][5], Value::Null);
+}
+
+#[test]
+fn t_get_mut_edits_in_place() {
+    let mut value = parse("server{port<u16>(8080)}").unwrap();
+
+    *value.get_mut("server").unwrap().get_mut("port").unwrap() = Value::U16(9090);
+    assert_eq!(value["server"]["port"], Value::U16(9090));
+}
+
+#[test]
+fn t_index_mut_assigns_existing_and_new_fields() {
+    let mut value = parse("server{port<u16>(8080)}").unwrap();
+
+    value["server"]["port"] = Value::U16(443);
+    value["server"]["host"] = Value::Str("example.org".to_string());
+
+    assert_eq!(value["server"]["port"], Value::U16(443));
+    let keys: Vec<&String> = value["server"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, vec!["port", "host"]);
+}
+
+#[test]
+fn t_index_mut_array_element() {
+    let mut value = parse("tags[a b c]").unwrap();
+
+    value["tags"][1] = Value::Str("z".to_string());
+    assert_eq!(value["tags"][1].as_str(), Some("z"));
+}
+
+#[test]
+#[should_panic(expected = "cannot index null")]
+fn t_index_mut_does_not_vivify_intermediates() {
+    let mut value = Value::Object(Map::new());
+    value["app"]["server"] = Value::Null;
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn t_index_mut_array_out_of_bounds() {
+    let mut value = Value::Array(vec![]);
+    value[0] = Value::Null;
+}
+
+#[test]
+fn t_insert_remove_push() {
+    let mut value = parse("cfg{a<u8>(1)b<u8>(2)c<u8>(3)list[1 2 3]}").unwrap();
+    let cfg = value.get_mut("cfg").unwrap();
+
+    assert_eq!(cfg.insert("a", Value::U8(10)), Some(Value::U8(1)));
+    assert_eq!(cfg.insert("d", Value::U8(4)), None);
+    assert_eq!(cfg.remove("b"), Some(Value::U8(2)));
+    assert_eq!(cfg.remove("missing"), None);
+
+    let list = cfg.get_mut("list").unwrap();
+    list.push(Value::I64(4));
+    assert_eq!(list.remove(0), Some(Value::I64(1)));
+    assert_eq!(list.remove(10), None);
+
+    let keys: Vec<&String> = cfg.as_object().unwrap().keys().collect();
+    assert_eq!(keys, vec!["a", "c", "list", "d"]);
+    assert_eq!(
+        cfg["list"],
+        Value::Array(vec![Value::I64(2), Value::I64(3), Value::I64(4)])
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot push onto object")]
+fn t_push_on_non_array_panics() {
+    Value::Object(Map::new()).push(Value::Null);
+}
+
+#[test]
+fn t_vivify_creates_intermediate_objects() {
+    let mut value = Value::Null;
+    value
+        .vivify("app")
+        .vivify("server")
+        .insert("port", Value::U16(8080));
+    value
+        .vivify("app")
+        .insert("name", Value::Str("demo".to_string()));
+
+    assert_eq!(value["app"]["server"]["port"], Value::U16(8080));
+    assert_eq!(value["app"]["name"].as_str(), Some("demo"));
+}
+
+#[test]
+fn t_map_entry_api() {
+    let mut map = Map::new();
+    *map.entry("hits".to_string()).or_insert(Value::U32(0)) = Value::U32(1);
+    map.entry("hits".to_string())
+        .and_modify(|v| *v = Value::U32(v.as_u32().unwrap() + 1))
+        .or_insert(Value::U32(0));
+
+    assert_eq!(map.get("hits"), Some(&Value::U32(2)));
+
+    match map.entry("hits".to_string()) {
+        gbln::map::Entry::Occupied(e) => assert_eq!(e.remove(), Value::U32(2)),
+        gbln::map::Entry::Vacant(_) => panic!("hits is present"),
+    }
+    assert!(map.is_empty());
+}
+
+#[test]
+fn t_map_lookups_survive_removal_and_reordering() {
+    let mut map = Map::new();
+    for i in 0..500u32 {
+        map.insert(format!("k{}", i), Value::U32(i));
+    }
+
+    // Remove from the front, middle and back, then every third field
+    for i in [0u32, 250, 499] {
+        assert_eq!(map.remove(&format!("k{}", i)), Some(Value::U32(i)));
+    }
+    map.retain(|_, v| v.as_u32().unwrap() % 3 != 0);
+    map.sort_keys();
+
+    let expected: Vec<u32> = (0..500)
+        .filter(|i| ![0, 250, 499].contains(i) && i % 3 != 0)
+        .collect();
+    assert_eq!(map.len(), expected.len());
+    for i in 0..500u32 {
+        let value = map.get(&format!("k{}", i));
+        assert_eq!(value.is_some(), expected.contains(&i), "k{}", i);
+    }
+    for (position, key) in map.keys().enumerate() {
+        assert_eq!(map.get_index_of(key), Some(position));
+    }
+}
+
+#[test]
+fn t_from_primitives() {
+    assert_eq!(Value::from(7u8), Value::U8(7));
+    assert_eq!(Value::from(-7i64), Value::I64(-7));
+    assert_eq!(Value::from(1.5f32), Value::F32(1.5));
+    assert_eq!(Value::from(true), Value::Bool(true));
+    assert_eq!(Value::from("hi"), Value::Str("hi".to_string()));
+    assert_eq!(
+        Value::from(String::from("hi")),
+        Value::Str("hi".to_string())
+    );
+}
+
+#[test]
+fn t_from_collections() {
+    assert_eq!(
+        Value::from(vec![1u16, 2]),
+        Value::Array(vec![Value::U16(1), Value::U16(2)])
+    );
+    assert_eq!(Value::from(None::<u8>), Value::Null);
+    assert_eq!(Value::from(Some("x")), Value::Str("x".to_string()));
+
+    let map = HashMap::from([("port".to_string(), 8080u16)]);
+    assert_eq!(Value::from(map)["port"], Value::U16(8080));
+}
+
+#[test]
+fn t_build_value_in_code() {
+    let mut server = Value::from(Map::new());
+    server.insert("host", "localhost");
+    server.insert("port", 8080u16);
+    server.insert("tags", vec!["a", "b"]);
+
+    assert_eq!(
+        gbln::to_string(&server),
+        "{host<s16>(localhost)port<u16>(8080)tags[<s2>(a)<s2>(b)]}"
+    );
+}
+
+#[test]
+fn t_try_from_integers_range_checked() {
+    assert_eq!(u8::try_from(&Value::I64(200)).unwrap(), 200);
+    assert_eq!(i64::try_from(&Value::U8(200)).unwrap(), 200);
+
+    let err = u8::try_from(&Value::I64(300)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+    assert!(err.message.contains("out of range for u8"));
+
+    let err = u32::try_from(&Value::I8(-1)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::IntegerOutOfRange);
+
+    let err = u32::try_from(&Value::Str("1".to_string())).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::TypeMismatch);
+}
+
+#[test]
+fn t_try_from_other_primitives() {
+    assert_eq!(f64::try_from(&Value::F32(0.5)).unwrap(), 0.5);
+    assert_eq!(f32::try_from(&Value::F64(0.5)).unwrap(), 0.5);
+    assert!(f32::try_from(&Value::F64(0.1)).is_err());
+    assert!(bool::try_from(&Value::Bool(true)).unwrap());
+    assert_eq!(String::try_from(&Value::from("x")).unwrap(), "x");
+    assert!(String::try_from(&Value::Null).is_err());
+}
+
+#[test]
+fn t_structural_equality_is_variant_sensitive() {
+    assert_ne!(Value::U8(5), Value::I64(5));
+    assert_eq!(Value::F64(f64::NAN), Value::F64(-f64::NAN));
+    assert_eq!(Value::F32(-0.0), Value::F32(0.0));
+    assert_ne!(Value::F32(1.0), Value::F64(1.0));
+}
+
+#[test]
+fn t_values_in_hash_set() {
+    let values = vec![
+        Value::U8(1),
+        Value::U8(1),
+        Value::I64(1),
+        Value::F64(f64::NAN),
+        Value::F64(f64::NAN),
+        Value::F64(0.0),
+        Value::F64(-0.0),
+        parse("{a<u8>(1)b<u8>(2)}").unwrap(),
+        parse("{b<u8>(2)a<u8>(1)}").unwrap(),
+    ];
+
+    let set: HashSet<Value> = values.into_iter().collect();
+    assert_eq!(set.len(), 5);
+}
+
+#[test]
+fn t_sort_and_dedup_array() {
+    let mut values = vec![
+        Value::Str("b".to_string()),
+        Value::I32(3),
+        Value::F64(f64::NAN),
+        Value::I32(-1),
+        Value::F64(f64::INFINITY),
+        Value::Str("a".to_string()),
+        Value::I32(3),
+        Value::Null,
+    ];
+    values.sort();
+    values.dedup();
+
+    assert_eq!(
+        values,
+        vec![
+            Value::I32(-1),
+            Value::I32(3),
+            Value::F64(f64::INFINITY),
+            Value::F64(f64::NAN),
+            Value::Str("a".to_string()),
+            Value::Str("b".to_string()),
+            Value::Null,
+        ]
+    );
+}
+
+#[test]
+fn t_object_ordering_ignores_insertion_order() {
+    let a = parse("{a<u8>(1)b<u8>(2)}").unwrap();
+    let b = parse("{b<u8>(2)a<u8>(1)}").unwrap();
+    let c = parse("{a<u8>(1)b<u8>(3)}").unwrap();
+
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert!(a < c);
+}
+
+#[test]
+fn t_semantic_eq_numbers_across_widths() {
+    assert!(Value::U8(5).semantic_eq(&Value::I64(5)));
+    assert!(Value::I8(-1).semantic_eq(&Value::I64(-1)));
+    assert!(!Value::I8(-1).semantic_eq(&Value::U64(u64::MAX)));
+    assert!(Value::F32(0.5).semantic_eq(&Value::F64(0.5)));
+    assert!(!Value::F32(0.1).semantic_eq(&Value::F64(0.1)));
+    assert!(Value::U16(8080).semantic_eq(&Value::F64(8080.0)));
+    assert!(!Value::U16(8080).semantic_eq(&Value::F64(8080.5)));
+    assert!(!Value::U8(1).semantic_eq(&Value::Bool(true)));
+}
+
+#[test]
+fn t_semantic_eq_typed_against_untyped_document() {
+    let typed = parse("app{port<u16>(8080)ratio<f32>(0.5)name<s8>(web)tags<u8>[1 2]}").unwrap();
+    let untyped = parse("app{port(8080)ratio(0.5)name(web)tags[1 2]}").unwrap();
+
+    assert_ne!(typed, untyped);
+    assert!(typed.semantic_eq(&untyped));
+
+    let different = parse("app{port(8081)ratio(0.5)name(web)tags[1 2]}").unwrap();
+    assert!(!typed.semantic_eq(&different));
+}
@@ -0,0 +1,157 @@
+// Copyright (c) 2025 Vivian Burkhard Voss
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for tree traversal
+
+use gbln::{parse, PathSegment, Value, ValuePath, Visit, Visitor, VisitorMut};
+
+const INPUT: &str =
+    "app{name<s8>(web)secret<s16>(hunter2)server{port<u16>(80)}users[{name<s4>(Ann)}]}";
+
+#[test]
+fn t_walk_document_order() {
+    let value = parse(INPUT).unwrap();
+    let paths: Vec<String> = value.walk().map(|(p, _)| p.to_string()).collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            "",
+            "app",
+            "app.name",
+            "app.secret",
+            "app.server",
+            "app.server.port",
+            "app.users",
+            "app.users[0]",
+            "app.users[0].name",
+        ]
+    );
+}
+
+#[test]
+fn t_walk_paths_resolve_with_get_path() {
+    let value = parse(INPUT).unwrap();
+
+    for (path, v) in value.walk() {
+        assert_eq!(value.get_path(&path.to_string()).unwrap(), v);
+    }
+}
+
+#[test]
+fn t_walk_skip_children() {
+    let value = parse(INPUT).unwrap();
+    let mut walk = value.walk();
+    let mut seen = Vec::new();
+
+    while let Some((path, _)) = walk.next() {
+        if path.last() == Some(&PathSegment::Key("server".to_string())) {
+            walk.skip_children();
+        }
+        seen.push(path.to_string());
+    }
+
+    assert!(seen.contains(&"app.server".to_string()));
+    assert!(!seen.contains(&"app.server.port".to_string()));
+    assert!(seen.contains(&"app.users[0].name".to_string()));
+}
+
+struct Collect {
+    entered: Vec<String>,
+    left: Vec<String>,
+}
+
+impl Visitor for Collect {
+    fn enter(&mut self, path: &ValuePath, value: &Value) -> Visit {
+        self.entered.push(path.to_string());
+        if value.is_array() {
+            Visit::SkipChildren
+        } else {
+            Visit::Continue
+        }
+    }
+
+    fn leave(&mut self, path: &ValuePath, _value: &Value) {
+        self.left.push(path.to_string());
+    }
+}
+
+#[test]
+fn t_visitor_enter_leave_and_prune() {
+    let value = parse(INPUT).unwrap();
+    let mut collect = Collect {
+        entered: Vec::new(),
+        left: Vec::new(),
+    };
+    value.accept(&mut collect);
+
+    assert!(collect.entered.contains(&"app.users".to_string()));
+    assert!(!collect.entered.contains(&"app.users[0]".to_string()));
+    assert_eq!(collect.left.first().map(String::as_str), Some("app.name"));
+    assert_eq!(collect.left.last().map(String::as_str), Some(""));
+}
+
+struct FindFirstPort(Option<String>);
+
+impl Visitor for FindFirstPort {
+    fn enter(&mut self, path: &ValuePath, _value: &Value) -> Visit {
+        if path.last() == Some(&PathSegment::Key("port".to_string())) {
+            self.0 = Some(path.to_string());
+            return Visit::Stop;
+        }
+        Visit::Continue
+    }
+}
+
+#[test]
+fn t_visitor_stop() {
+    let value = parse(INPUT).unwrap();
+    let mut finder = FindFirstPort(None);
+    value.accept(&mut finder);
+
+    assert_eq!(finder.0.as_deref(), Some("app.server.port"));
+}
+
+struct Redact;
+
+impl VisitorMut for Redact {
+    fn enter(&mut self, path: &ValuePath, value: &mut Value) -> Visit {
+        if path.last() == Some(&PathSegment::Key("secret".to_string())) {
+            *value = Value::Str("***".to_string());
+        }
+        Visit::Continue
+    }
+}
+
+#[test]
+fn t_visitor_mut_redaction() {
+    let mut value = parse(INPUT).unwrap();
+    value.accept_mut(&mut Redact);
+
+    assert_eq!(value["app"]["secret"].as_str(), Some("***"));
+    assert_eq!(value["app"]["name"].as_str(), Some("web"));
+}
+
+struct TightenIntegers;
+
+impl VisitorMut for TightenIntegers {
+    fn enter(&mut self, _path: &ValuePath, _value: &mut Value) -> Visit {
+        Visit::Continue
+    }
+
+    fn leave(&mut self, _path: &ValuePath, value: &mut Value) {
+        if let Some(n) = value.as_u8() {
+            *value = Value::U8(n);
+        }
+    }
+}
+
+#[test]
+fn t_visitor_mut_bottom_up() {
+    let mut value = parse("cfg{workers(4)big(1000)list[1 2]}").unwrap();
+    value.accept_mut(&mut TightenIntegers);
+
+    assert_eq!(value["cfg"]["workers"], Value::U8(4));
+    assert_eq!(value["cfg"]["big"], Value::I64(1000));
+    assert_eq!(value["cfg"]["list"][1], Value::U8(2));
+}